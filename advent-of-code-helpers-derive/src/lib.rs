@@ -0,0 +1,430 @@
+//! Derive macro generating line-oriented [`FromStr`](std::str::FromStr) implementations, so types
+//! plug straight into `ParsingLineIterator`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, Ident, Lit,
+    LitStr, Member, Token, Type,
+};
+
+/// Derive [`FromStr`](std::str::FromStr) from a line format.
+///
+/// On structs, and on enum variants with fields, `#[line(format = "...")]` describes the line
+/// with `{field}` placeholders (`{0}` for tuple fields) separated by literal text. Every field is
+/// parsed using its own `FromStr` implementation. A placeholder takes everything up to the first
+/// occurrence of the literal after it.
+///
+/// Unit enum variants are matched by the strings and characters in `#[line("add", '+')]`, or by
+/// their name if there is no attribute. Variants are tried in declaration order, so a format that
+/// matches anything should come last.
+///
+/// Generic parameters are supported, every field type gets a `FromStr` bound with a `Debug` error.
+///
+/// ```ignore
+/// #[derive(LineParse)]
+/// #[line(format = "{op_type} {op_value}")]
+/// struct Op {
+///     op_type: OpType,
+///     op_value: i32,
+/// }
+///
+/// #[derive(LineParse)]
+/// enum OpType {
+///     #[line("add")]
+///     Add,
+///     #[line("mul", '*')]
+///     Multiply,
+/// }
+/// ```
+#[proc_macro_derive(LineParse, attributes(line))]
+pub fn derive_line_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let format = match line_attribute(&input.attrs)? {
+                Some(LineAttribute::Format(format)) => format,
+                Some(LineAttribute::Values(_)) => {
+                    return Err(Error::new_spanned(
+                        name,
+                        "structs need #[line(format = \"...\")]",
+                    ))
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        name,
+                        "missing #[line(format = \"...\")] attribute",
+                    ))
+                }
+            };
+
+            expand_format(name, &format, &data.fields, quote!(Self))?
+        }
+        Data::Enum(data) => {
+            let mut attempts = Vec::new();
+            let mut expected = Vec::new();
+
+            for variant in &data.variants {
+                let variant_name = &variant.ident;
+
+                match line_attribute(&variant.attrs)? {
+                    Some(LineAttribute::Format(format)) => {
+                        let parse = expand_format(
+                            name,
+                            &format,
+                            &variant.fields,
+                            quote!(Self::#variant_name),
+                        )?;
+
+                        attempts.push(quote! {
+                            let attempt = (|| -> ::std::result::Result<Self, ::std::string::String> {
+                                #parse
+                            })();
+
+                            match attempt {
+                                ::std::result::Result::Ok(value) => {
+                                    return ::std::result::Result::Ok(value)
+                                }
+                                ::std::result::Result::Err(error) => errors.push(error),
+                            }
+                        });
+                        expected.push(format.value());
+                    }
+                    attribute => {
+                        if !matches!(variant.fields, Fields::Unit) {
+                            return Err(Error::new_spanned(
+                                variant,
+                                "variants with fields need #[line(format = \"...\")]",
+                            ));
+                        }
+
+                        let values = match attribute {
+                            Some(LineAttribute::Values(values)) => values,
+                            _ => vec![variant_name.to_string()],
+                        };
+
+                        expected.extend(values.iter().cloned());
+                        attempts.push(quote! {
+                            if ::std::matches!(s, #(#values)|*) {
+                                return ::std::result::Result::Ok(Self::#variant_name);
+                            }
+                        });
+                    }
+                }
+            }
+
+            let expected = expected
+                .iter()
+                .map(|value| format!("{value:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let type_name = name.to_string();
+
+            quote! {
+                #[allow(unused_mut)]
+                let mut errors: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+
+                #(#attempts)*
+
+                let mut message = ::std::format!(
+                    "{}: invalid value {:?}, expected one of {}",
+                    #type_name,
+                    s,
+                    #expected
+                );
+
+                for error in errors {
+                    message.push_str("; ");
+                    message.push_str(&error);
+                }
+
+                ::std::result::Result::Err(message)
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "LineParse cannot be derived for unions",
+            ))
+        }
+    };
+
+    if !generics.params.is_empty() {
+        let field_types = match &input.data {
+            Data::Struct(data) => data.fields.iter().map(|field| &field.ty).collect(),
+            Data::Enum(data) => data
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+                .collect(),
+            Data::Union(_) => Vec::new(),
+        };
+        let where_clause = generics.make_where_clause();
+
+        for ty in field_types {
+            where_clause.predicates.push(parse_quote! {
+                #ty: ::std::str::FromStr
+            });
+            where_clause.predicates.push(parse_quote! {
+                <#ty as ::std::str::FromStr>::Err: ::std::fmt::Debug
+            });
+        }
+    }
+
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #type_generics #where_clause {
+            type Err = ::std::string::String;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+    })
+}
+
+enum LineAttribute {
+    Format(LitStr),
+    Values(Vec<String>),
+}
+
+/// Read the `#[line(...)]` attribute, if there is one.
+fn line_attribute(attributes: &[Attribute]) -> syn::Result<Option<LineAttribute>> {
+    let mut found = None;
+
+    for attribute in attributes {
+        if !attribute.path().is_ident("line") {
+            continue;
+        }
+
+        if found.is_some() {
+            return Err(Error::new_spanned(attribute, "duplicate #[line] attribute"));
+        }
+
+        found = Some(attribute.parse_args_with(|input: ParseStream| {
+            if input.peek(Ident) {
+                let key: Ident = input.parse()?;
+
+                if key != "format" {
+                    return Err(Error::new(key.span(), "expected `format = \"...\"`"));
+                }
+
+                input.parse::<Token![=]>()?;
+
+                return Ok(LineAttribute::Format(input.parse()?));
+            }
+
+            let literals = Punctuated::<Lit, Token![,]>::parse_terminated(input)?;
+            let mut values = Vec::new();
+
+            for literal in literals {
+                match literal {
+                    Lit::Str(value) => values.push(value.value()),
+                    Lit::Char(value) => values.push(value.value().to_string()),
+                    other => {
+                        return Err(Error::new_spanned(
+                            other,
+                            "expected a string or character literal",
+                        ))
+                    }
+                }
+            }
+
+            if values.is_empty() {
+                return Err(input.error("expected at least one string or character literal"));
+            }
+
+            Ok(LineAttribute::Values(values))
+        })?);
+    }
+
+    Ok(found)
+}
+
+enum Piece {
+    Literal(String),
+    Field(String),
+}
+
+/// Split a format into literal text and placeholders, `{{` and `}}` are literal braces.
+fn split_format(format: &LitStr) -> syn::Result<Vec<Piece>> {
+    let value = format.value();
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(Error::new_spanned(format, "unclosed placeholder")),
+                    }
+                }
+
+                if literal.is_empty() && matches!(pieces.last(), Some(Piece::Field(_))) {
+                    return Err(Error::new_spanned(
+                        format,
+                        "placeholders must be separated by literal text",
+                    ));
+                }
+
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+
+                pieces.push(Piece::Field(field.trim().to_string()));
+            }
+            '}' => return Err(Error::new_spanned(format, "unmatched `}`")),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+
+    Ok(pieces)
+}
+
+/// Generate the statements parsing `s` using the format, ending in constructing `constructor`.
+fn expand_format(
+    type_name: &Ident,
+    format: &LitStr,
+    fields: &Fields,
+    constructor: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let type_name = type_name.to_string();
+    let format_value = format.value();
+    let pieces = split_format(format)?;
+
+    // Every field of the type, by the name used in the format
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let (placeholder, member) = match &field.ident {
+                Some(ident) => (ident.to_string(), Member::Named(ident.clone())),
+                None => (index.to_string(), Member::Unnamed(index.into())),
+            };
+
+            (placeholder, member, &field.ty)
+        })
+        .collect::<Vec<(String, Member, &Type)>>();
+
+    let mut used = Vec::new();
+    let mut statements = Vec::new();
+
+    for (i, piece) in pieces.iter().enumerate() {
+        match piece {
+            Piece::Literal(literal) => {
+                statements.push(quote! {
+                    let rest = rest.strip_prefix(#literal).ok_or_else(|| {
+                        ::std::format!(
+                            "{}: expected {:?} at {:?} in {:?} (format {:?})",
+                            #type_name, #literal, rest, s, #format_value
+                        )
+                    })?;
+                });
+            }
+            Piece::Field(placeholder) => {
+                let Some((_, _, ty)) = members.iter().find(|(name, ..)| name == placeholder) else {
+                    return Err(Error::new_spanned(
+                        format,
+                        format!("`{placeholder}` is not a field"),
+                    ));
+                };
+
+                if used.contains(placeholder) {
+                    return Err(Error::new_spanned(
+                        format,
+                        format!("`{placeholder}` is used more than once"),
+                    ));
+                }
+                used.push(placeholder.clone());
+
+                let variable = format_ident!("field_{}", placeholder, span = Span::call_site());
+
+                let split = match pieces.get(i + 1) {
+                    Some(Piece::Literal(next)) => quote! {
+                        let (text, rest) = match rest.find(#next) {
+                            ::std::option::Option::Some(end) => (&rest[..end], &rest[end..]),
+                            ::std::option::Option::None => {
+                                return ::std::result::Result::Err(::std::format!(
+                                    "{}: expected {:?} after {{{}}} in {:?} (format {:?})",
+                                    #type_name, #next, #placeholder, s, #format_value
+                                ))
+                            }
+                        };
+                    },
+                    _ => quote! {
+                        let (text, rest) = (rest, "");
+                    },
+                };
+
+                statements.push(quote! {
+                    #split
+                    let #variable: #ty = ::std::str::FromStr::from_str(text).map_err(|error| {
+                        ::std::format!(
+                            "{}: invalid {{{}}} {:?} in {:?}: {:?}",
+                            #type_name, #placeholder, text, s, error
+                        )
+                    })?;
+                });
+            }
+        }
+    }
+
+    if let Some((placeholder, ..)) = members.iter().find(|(name, ..)| !used.contains(name)) {
+        return Err(Error::new_spanned(
+            format,
+            format!("field `{placeholder}` does not appear in the format"),
+        ));
+    }
+
+    let initializers = members.iter().map(|(placeholder, member, _)| {
+        let variable = format_ident!("field_{}", placeholder, span = Span::call_site());
+
+        quote!(#member: #variable)
+    });
+
+    Ok(quote! {
+        let rest: &str = s;
+        #(#statements)*
+
+        if !rest.is_empty() {
+            return ::std::result::Result::Err(::std::format!(
+                "{}: unexpected {:?} at the end of {:?} (format {:?})",
+                #type_name, rest, s, #format_value
+            ));
+        }
+
+        ::std::result::Result::Ok(#constructor { #(#initializers),* })
+    })
+}
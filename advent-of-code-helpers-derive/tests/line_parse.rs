@@ -0,0 +1,222 @@
+use advent_of_code_helpers::parsing_line_iterator::ParsingLineIterator;
+use advent_of_code_helpers::pattern::Parsed;
+use advent_of_code_helpers_derive::LineParse;
+use std::str::FromStr;
+
+#[derive(Debug, Eq, PartialEq, LineParse)]
+enum OpType {
+    #[line("add")]
+    Add,
+    #[line("mul", '*')]
+    Multiply,
+    #[line("div")]
+    Divide,
+    #[line("sub")]
+    Subtract,
+}
+
+#[derive(Debug, Eq, PartialEq, LineParse)]
+#[line(format = "{op_type} {op_value}")]
+struct Op {
+    op_type: OpType,
+    op_value: i32,
+}
+
+#[test]
+fn it_parses_custom_structs() {
+    let input = r#"add 10
+mul 2
+div 3
+sub 12"#;
+
+    let ops = ParsingLineIterator::<Op>::from(input).collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            Op {
+                op_type: OpType::Add,
+                op_value: 10,
+            },
+            Op {
+                op_type: OpType::Multiply,
+                op_value: 2,
+            },
+            Op {
+                op_type: OpType::Divide,
+                op_value: 3,
+            },
+            Op {
+                op_type: OpType::Subtract,
+                op_value: 12,
+            }
+        ],
+        ops
+    )
+}
+
+#[test]
+fn derived_types_split_when_wrapped_in_parsed() {
+    let ops = ParsingLineIterator::<Vec<Parsed<Op>>>::from("add 10, mul 2\nsub 1")
+        .split(",")
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            vec![
+                Parsed(Op {
+                    op_type: OpType::Add,
+                    op_value: 10,
+                }),
+                Parsed(Op {
+                    op_type: OpType::Multiply,
+                    op_value: 2,
+                })
+            ],
+            vec![Parsed(Op {
+                op_type: OpType::Subtract,
+                op_value: 1,
+            })]
+        ],
+        ops
+    );
+}
+
+#[test]
+fn enum_variants_accept_chars_and_default_to_their_name() {
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    enum Cell {
+        #[line('#')]
+        Wall,
+        Open,
+    }
+
+    assert_eq!(Ok(OpType::Multiply), "*".parse());
+    assert_eq!(Ok(Cell::Wall), "#".parse());
+    assert_eq!(Ok(Cell::Open), "Open".parse());
+}
+
+#[test]
+fn it_parses_formats_with_literals_and_tuple_fields() {
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    #[line(format = "Sensor at x={0}, y={1}")]
+    struct Sensor(i64, i64);
+
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    #[line(format = "{{{name}}}: {low}-{high}")]
+    struct Rule {
+        low: u32,
+        high: u32,
+        name: String,
+    }
+
+    assert_eq!(Ok(Sensor(2, -18)), "Sensor at x=2, y=-18".parse());
+    assert_eq!(
+        Ok(Rule {
+            low: 1,
+            high: 3,
+            name: "a b".to_string()
+        }),
+        "{a b}: 1-3".parse()
+    );
+}
+
+#[test]
+fn enum_variants_can_have_formats() {
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    enum Instruction {
+        #[line("noop")]
+        Noop,
+        #[line(format = "addx {0}")]
+        AddX(i32),
+        #[line(format = "move {count} from {from} to {to}")]
+        Move {
+            count: usize,
+            from: usize,
+            to: usize,
+        },
+    }
+
+    assert_eq!(Ok(Instruction::Noop), "noop".parse());
+    assert_eq!(Ok(Instruction::AddX(-5)), "addx -5".parse());
+    assert_eq!(
+        Ok(Instruction::Move {
+            count: 3,
+            from: 1,
+            to: 2
+        }),
+        "move 3 from 1 to 2".parse()
+    );
+}
+
+#[test]
+fn enum_variants_are_tried_in_declaration_order() {
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    enum Command {
+        #[line("stop")]
+        Stop,
+        #[line(format = "{0}")]
+        Say(String),
+    }
+
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    enum Shadowed {
+        #[line(format = "{0}")]
+        Say(String),
+        #[line("stop")]
+        Stop,
+    }
+
+    assert_eq!(Ok(Command::Stop), "stop".parse());
+    assert_eq!(Ok(Command::Say("go".to_string())), "go".parse());
+    assert_eq!(Ok(Shadowed::Say("stop".to_string())), "stop".parse());
+}
+
+#[test]
+fn generic_types_bound_their_fields() {
+    #[derive(Debug, Eq, PartialEq, LineParse)]
+    #[line(format = "{low}-{high}")]
+    struct Range<T> {
+        low: T,
+        high: T,
+    }
+
+    assert_eq!(Ok(Range { low: 2, high: 4 }), "2-4".parse::<Range<u8>>());
+    assert_eq!(
+        Ok(Range {
+            low: 'a',
+            high: 'c'
+        }),
+        "a-c".parse()
+    );
+}
+
+#[test]
+fn it_reports_helpful_errors() {
+    assert_eq!(
+        Err(
+            r#"OpType: invalid value "pow", expected one of "add", "mul", "*", "div", "sub""#
+                .to_string()
+        ),
+        OpType::from_str("pow")
+    );
+
+    assert_eq!(
+        Err(
+            r#"Op: expected " " after {op_type} in "add10" (format "{op_type} {op_value}")"#
+                .to_string()
+        ),
+        Op::from_str("add10")
+    );
+
+    let error = Op::from_str("add ten").unwrap_err();
+    assert!(
+        error.starts_with(r#"Op: invalid {op_value} "ten" in "add ten": ParseIntError"#),
+        "{error}"
+    );
+
+    let error = Op::from_str("pow 10").unwrap_err();
+    assert!(
+        error.starts_with(r#"Op: invalid {op_type} "pow" in "pow 10": "OpType: invalid value"#),
+        "{error}"
+    );
+}
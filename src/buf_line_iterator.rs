@@ -0,0 +1,249 @@
+use crate::line_iterator::LineIteratorSettings;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, StdinLock};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Owned counterpart of [`crate::line_iterator::LineIterator`] that streams lines from any
+/// [`BufRead`], such as a file, stdin or a [`io::Cursor`], trimming them the same way.
+pub struct BufLineIterator<R> {
+    reader: R,
+    settings: LineIteratorSettings,
+    buffer: String,
+    line_number: usize,
+}
+
+impl<R> BufLineIterator<R>
+where
+    R: BufRead,
+{
+    pub fn from(reader: R) -> Self {
+        Self::from_settings(reader, LineIteratorSettings::default())
+    }
+
+    pub fn from_settings(reader: R, settings: LineIteratorSettings) -> Self {
+        Self {
+            reader,
+            settings,
+            buffer: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// 1-based number of the last line read, counting skipped lines.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl BufLineIterator<BufReader<File>> {
+    pub fn from_file<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self::from(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl BufLineIterator<StdinLock<'static>> {
+    pub fn from_stdin() -> Self {
+        Self::from(io::stdin().lock())
+    }
+}
+
+impl<R> Iterator for BufLineIterator<R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+
+            let read = self.reader.read_line(&mut self.buffer);
+
+            if !matches!(read, Ok(0)) {
+                self.line_number += 1;
+            }
+
+            match read {
+                Ok(0) => return None,
+                Ok(_) => {
+                    if let Some(line) = self.settings.apply(&self.buffer) {
+                        return Some(Ok(line.to_string()));
+                    }
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+/// Streaming counterpart of [`crate::parsing_line_iterator::ParsingLineIterator`], panics if a line
+/// cannot be read or parsed.
+pub struct BufParsingLineIterator<R, T> {
+    line_iterator: BufLineIterator<R>,
+    marker: PhantomData<T>,
+}
+
+impl<R, T> BufParsingLineIterator<R, T>
+where
+    R: BufRead,
+{
+    pub fn from(reader: R) -> Self {
+        Self {
+            line_iterator: BufLineIterator::from(reader),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn from_settings(reader: R, settings: LineIteratorSettings) -> Self {
+        Self {
+            line_iterator: BufLineIterator::from_settings(reader, settings),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> BufParsingLineIterator<BufReader<File>, T> {
+    pub fn from_file<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(BufLineIterator::from_file(path)?.into())
+    }
+}
+
+impl<T> BufParsingLineIterator<StdinLock<'static>, T> {
+    pub fn from_stdin() -> Self {
+        BufLineIterator::from_stdin().into()
+    }
+}
+
+impl<R, T> From<BufLineIterator<R>> for BufParsingLineIterator<R, T> {
+    fn from(line_iterator: BufLineIterator<R>) -> Self {
+        Self {
+            line_iterator,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> Iterator for BufParsingLineIterator<R, T>
+where
+    R: BufRead,
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line_iterator.next().map(|line| {
+            let line_number = self.line_iterator.line_number();
+            let line =
+                line.unwrap_or_else(|error| panic!("failed to read line {line_number}: {error}"));
+
+            line.parse().unwrap_or_else(|error| {
+                panic!("failed to parse line {line_number} {line:?}: {error:?}")
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_iterator::{LineIterator, TrimMode};
+    use std::io::{Cursor, Write};
+
+    #[test]
+    fn it_matches_line_iterator() {
+        let input = "  a \r\n\tb\n\n# c\nc  ";
+
+        for trim_mode in [
+            TrimMode::All,
+            TrimMode::LineEndOnly,
+            TrimMode::None,
+            TrimMode::Chars(vec![' ', 'c']),
+        ] {
+            for skip_empty in [false, true] {
+                let settings = LineIteratorSettings {
+                    trim_mode: trim_mode.clone(),
+                    skip_empty,
+                    comment_prefix: Some("#".to_string()),
+                };
+
+                let expected =
+                    LineIterator::from_settings(input, settings.clone()).collect::<Vec<_>>();
+                let lines = BufLineIterator::from_settings(Cursor::new(input), settings)
+                    .collect::<io::Result<Vec<_>>>()
+                    .unwrap();
+
+                assert_eq!(expected, lines);
+            }
+        }
+    }
+
+    #[test]
+    fn it_parses_lines() {
+        let values =
+            BufParsingLineIterator::<_, i64>::from(Cursor::new("1\n-2\n3\n")).collect::<Vec<_>>();
+
+        assert_eq!(vec![1, -2, 3], values);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse line 4 \"x\"")]
+    fn it_panics_with_the_line_number() {
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            ..Default::default()
+        };
+
+        BufParsingLineIterator::<_, u32>::from_settings(Cursor::new("1\n\n2\nx\n"), settings)
+            .for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read line 2: ")]
+    fn it_panics_on_unreadable_lines() {
+        BufParsingLineIterator::<_, String>::from(Cursor::new(b"ok\n\xff\n".to_vec()))
+            .for_each(drop);
+    }
+
+    #[test]
+    fn it_reports_invalid_utf8() {
+        let mut lines = BufLineIterator::from(Cursor::new(b"ok\n\xff\n".to_vec()));
+
+        assert_eq!("ok", lines.next().unwrap().unwrap());
+        assert!(lines.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn it_reads_files() {
+        let path = std::env::temp_dir().join(format!(
+            "advent-of-code-helpers-{}-buf-line-iterator.txt",
+            std::process::id()
+        ));
+        File::create(&path).unwrap().write_all(b"10\n20\n").unwrap();
+
+        let lines = LineIterator::from_file(&path)
+            .unwrap()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        let sum = BufParsingLineIterator::<_, u32>::from_file(&path)
+            .unwrap()
+            .sum::<u32>();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(vec!["10", "20"], lines);
+        assert_eq!(30, sum);
+
+        assert!(LineIterator::from_file(&path).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::line_iterator::LineIterator;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+fn parse_cell<T>(cell: &str, line_number: usize) -> T
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    cell.parse()
+        .unwrap_or_else(|error| panic!("line {line_number}: invalid value {cell:?}: {error:?}"))
+}
+
+/// Split every line on whitespace and collect the values by column, so `3   4\n4   3` yields
+/// `[[3, 4], [4, 3]]`.
+///
+/// Panics if a value cannot be parsed, or a line has a different number of values than the first.
+pub fn columns<T>(lines: LineIterator) -> Vec<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    let mut columns = Vec::<Vec<T>>::new();
+
+    for line in lines.with_positions() {
+        let line_number = line.index + 1;
+        let values = line
+            .text
+            .split_whitespace()
+            .map(|cell| parse_cell(cell, line_number))
+            .collect::<Vec<T>>();
+
+        if columns.is_empty() {
+            columns.resize_with(values.len(), Vec::new);
+        }
+
+        if values.len() != columns.len() {
+            panic!(
+                "line {line_number} {:?}: expected {} columns, found {}",
+                line.text,
+                columns.len(),
+                values.len()
+            );
+        }
+
+        for (column, value) in columns.iter_mut().zip(values) {
+            column.push(value);
+        }
+    }
+
+    columns
+}
+
+/// Cut every line into columns of fixed character widths, where the last width repeats until the
+/// end of the longest line, and collect the values by column. Each cell is trimmed, and cells that
+/// are blank or missing because a line is shorter become `None`.
+///
+/// Lines are cut before trimming them, only their line ending is removed, so leading whitespace
+/// keeps the cells aligned regardless of the trim mode.
+///
+/// Panics if a value cannot be parsed, or `widths` is empty or contains a zero.
+pub fn fixed_width_columns<T>(lines: LineIterator, widths: &[usize]) -> Vec<Vec<Option<T>>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    assert!(
+        !widths.is_empty() && !widths.contains(&0),
+        "column widths must be positive, got {widths:?}"
+    );
+
+    let rows = lines
+        .with_positions()
+        .map(|line| {
+            let line_number = line.index + 1;
+            let chars = line
+                .raw
+                .trim_end_matches(['\r', '\n'])
+                .chars()
+                .collect::<Vec<_>>();
+
+            let mut cells = Vec::new();
+            let mut start = 0;
+
+            while start < chars.len() {
+                let width = widths[cells.len().min(widths.len() - 1)];
+                let end = (start + width).min(chars.len());
+                let cell = chars[start..end].iter().collect::<String>();
+                let cell = cell.trim();
+
+                cells.push((!cell.is_empty()).then(|| parse_cell(cell, line_number)));
+                start = end;
+            }
+
+            cells
+        })
+        .collect::<Vec<Vec<Option<T>>>>();
+
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut columns = (0..column_count)
+        .map(|_| Vec::with_capacity(rows.len()))
+        .collect::<Vec<_>>();
+
+    for row in rows {
+        let mut cells = row.into_iter();
+
+        for column in columns.iter_mut() {
+            column.push(cells.next().flatten());
+        }
+    }
+
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_on_whitespace() {
+        let input = "3   4\n4   3\n2   5\n";
+
+        assert_eq!(
+            vec![vec![3, 4, 2], vec![4, 3, 5]],
+            LineIterator::from(input).columns::<u32>()
+        );
+        assert_eq!(Vec::<Vec<u32>>::new(), LineIterator::from("").columns());
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 \"4\": expected 2 columns, found 1")]
+    fn it_panics_on_ragged_lines() {
+        LineIterator::from("3 4\n4\n").columns::<u32>();
+    }
+
+    #[test]
+    fn it_keeps_empty_fixed_width_slots() {
+        let input = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n";
+
+        assert_eq!(
+            vec![
+                vec![Some(123), Some(45), Some(6)],
+                vec![Some(328), Some(64), Some(98)],
+                vec![Some(51), Some(387), Some(215)],
+                vec![Some(64), Some(23), Some(314)],
+            ],
+            LineIterator::from(input).fixed_width_columns::<u32>(&[4])
+        );
+        assert_eq!(
+            vec![vec![Some(1), None], vec![None, Some(2)]],
+            LineIterator::from("1  \n   2").fixed_width_columns::<u8>(&[2])
+        );
+    }
+
+    #[test]
+    fn short_lines_yield_none() {
+        let input = "    [D]\n[N] [C]\n[Z] [M] [P]\n";
+
+        assert_eq!(
+            vec![
+                vec![None, Some("[N]".to_string()), Some("[Z]".to_string())],
+                vec![
+                    Some("[D]".to_string()),
+                    Some("[C]".to_string()),
+                    Some("[M]".to_string())
+                ],
+                vec![None, None, Some("[P]".to_string())],
+            ],
+            LineIterator::from(input).fixed_width_columns::<String>(&[4])
+        );
+    }
+
+    #[test]
+    fn it_uses_each_width_in_turn() {
+        assert_eq!(
+            vec![
+                vec![Some(1), Some(22)],
+                vec![Some(333), None],
+                vec![Some(4), None]
+            ],
+            LineIterator::from("1 333 4\n22\n").fixed_width_columns::<u32>(&[2, 4])
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Iterates over the text of the integers in a line, including their minus signs.
+///
+/// A `-` is only taken as a minus sign if it is directly followed by a digit and not preceded by a
+/// letter or digit, so `x=-5` yields `-5` while `a-5` and `3-5` yield `5` and `3, 5`.
+pub struct IntTokens<'a> {
+    line: &'a str,
+    position: usize,
+}
+
+impl<'a> IntTokens<'a> {
+    pub fn from(line: &'a str) -> Self {
+        Self { line, position: 0 }
+    }
+}
+
+impl<'a> Iterator for IntTokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.line.as_bytes();
+        let start = self.position + bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+
+        let negative = start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric());
+
+        let end = start
+            + bytes[start..]
+                .iter()
+                .position(|byte| !byte.is_ascii_digit())
+                .unwrap_or(bytes.len() - start);
+
+        self.position = end;
+
+        Some(&self.line[if negative { start - 1 } else { start }..end])
+    }
+}
+
+/// Iterates over the integers in a line without allocating, ignoring any text around them. See
+/// [`IntTokens`] for which integers are found.
+///
+/// Panics if an integer does not fit in `T`.
+pub struct IntIterator<'a, T> {
+    tokens: IntTokens<'a>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> IntIterator<'a, T> {
+    pub fn from(line: &'a str) -> Self {
+        Self {
+            tokens: IntTokens::from(line),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IntIterator<'a, T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tokens
+            .next()
+            .map(|token| parse_int(token).unwrap_or_else(|error| panic!("{error}")))
+    }
+}
+
+/// Collections that can be built from the integers in a line, see [`extract_ints`].
+pub trait FromInts: Sized {
+    fn from_ints(line: &str) -> Result<Self, String>;
+}
+
+fn parse_int<T>(token: &str) -> Result<T, String>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    token
+        .parse()
+        .map_err(|error| format!("invalid integer {token:?}: {error:?}"))
+}
+
+impl<T> FromInts for Vec<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    fn from_ints(line: &str) -> Result<Self, String> {
+        IntTokens::from(line).map(parse_int).collect()
+    }
+}
+
+impl<T, const N: usize> FromInts for [T; N]
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    fn from_ints(line: &str) -> Result<Self, String> {
+        let values = Vec::<T>::from_ints(line)?;
+        let found = values.len();
+
+        values
+            .try_into()
+            .map_err(|_| wrong_amount(N, found, "integers", line))
+    }
+}
+
+/// The error for finding `found` instead of `expected` values, like `integers` or `parts`.
+pub(crate) fn wrong_amount(expected: usize, found: usize, what: &str, text: &str) -> String {
+    format!("expected {expected} {what}, found {found} in {text:?}")
+}
+
+macro_rules! impl_from_ints {
+    ($count:expr; $($t:ident),*) => {
+        impl<$($t),*> FromInts for ($($t,)*)
+        where
+            $($t: FromStr, <$t as FromStr>::Err: Debug),*
+        {
+            fn from_ints(line: &str) -> Result<Self, String> {
+                let found = IntTokens::from(line).count();
+
+                if found != $count {
+                    return Err(wrong_amount($count, found, "integers", line));
+                }
+
+                let mut tokens = IntTokens::from(line);
+
+                Ok(($(parse_int::<$t>(tokens.next().unwrap())?,)*))
+            }
+        }
+    };
+}
+
+impl_from_ints!(1; A);
+impl_from_ints!(2; A, B);
+impl_from_ints!(3; A, B, C);
+impl_from_ints!(4; A, B, C, D);
+impl_from_ints!(5; A, B, C, D, E);
+impl_from_ints!(6; A, B, C, D, E, F);
+impl_from_ints!(7; A, B, C, D, E, F, G);
+impl_from_ints!(8; A, B, C, D, E, F, G, H);
+
+/// Pull all integers out of a line into a [`Vec`], an array or a tuple, see [`IntIterator`] for
+/// which integers are found.
+///
+/// Panics if an integer does not fit the type, or the amount of integers does not match the array
+/// or tuple.
+pub fn extract_ints<T>(line: &str) -> T
+where
+    T: FromInts,
+{
+    T::from_ints(line).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Zero allocation alternative to [`extract_ints`].
+pub fn iter_ints<T>(line: &str) -> IntIterator<'_, T> {
+    IntIterator::from(line)
+}
+
+/// Wrapper implementing [`FromStr`] by extracting integers, so a
+/// [`crate::parsing_line_iterator::ParsingLineIterator`] can yield them per line, like
+/// `ParsingLineIterator::<Ints<[i64; 4]>>`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Ints<T>(pub T);
+
+impl<T> FromStr for Ints<T>
+where
+    T: FromInts,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        T::from_ints(s).map(Ints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_iterator::LineIterator;
+    use crate::parsing_line_iterator::ParsingLineIterator;
+
+    #[test]
+    fn it_extracts_signed_integers() {
+        assert_eq!(
+            vec![2, 18, -2, 15],
+            extract_ints::<Vec<i64>>("Sensor at x=2, y=18: closest beacon is at x=-2, y=15")
+        );
+        assert_eq!(vec![-7, 3], extract_ints::<Vec<i32>>("-7 and 3."));
+    }
+
+    #[test]
+    fn it_does_not_treat_hyphens_as_minus_signs() {
+        assert_eq!(vec![2, 4, 6, 8], extract_ints::<Vec<u32>>("2-4,6-8"));
+        assert_eq!(vec![12], extract_ints::<Vec<i32>>("foo-12"));
+        assert_eq!(vec![-3], extract_ints::<Vec<i32>>("x=--3"));
+    }
+
+    #[test]
+    fn it_extracts_arrays_and_tuples() {
+        assert_eq!([1, 2, 3], extract_ints::<[u8; 3]>("move 1 from 2 to 3"));
+        assert_eq!(
+            (1_u8, -2_i64, 300_u16),
+            extract_ints::<(u8, i64, u16)>("a1, b=-2, c300")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid integer \"300\": ParseIntError")]
+    fn iterating_panics_on_overflow() {
+        IntIterator::<u8>::from("1, 300").for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 integers, found 3")]
+    fn it_checks_the_amount_of_integers() {
+        extract_ints::<[u8; 2]>("1 2 3");
+    }
+
+    #[test]
+    fn iter_ints_works() {
+        assert_eq!(-4, iter_ints::<i32>("1, -2, 3, -6").sum::<i32>());
+        assert_eq!(None, iter_ints::<i32>("no numbers").next());
+    }
+
+    #[test]
+    fn it_integrates_with_line_iterators() {
+        let input = "p=0,4 v=3,-3\np=6,3 v=-1,-3";
+
+        assert_eq!(
+            vec![[0, 4, 3, -3], [6, 3, -1, -3]],
+            LineIterator::from(input)
+                .ints::<[i64; 4]>()
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![Ints((0, 4, 3, -3)), Ints((6, 3, -1, -3))],
+            ParsingLineIterator::<Ints<(u8, u8, i8, i8)>>::from(input).collect::<Vec<_>>()
+        );
+    }
+}
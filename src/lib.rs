@@ -1,3 +1,17 @@
+pub mod buf_line_iterator;
+pub mod columns;
+pub mod ints;
 pub mod line_iterator;
 pub mod parsing_line_iterator;
+pub mod pathfinding;
+pub mod pattern;
+pub mod point;
+pub mod regions;
+pub mod sections;
+pub mod sparse_grid;
+pub mod split_parsing_iterator;
+pub mod try_parsing_line_iterator;
 pub mod vec2d;
+
+#[cfg(feature = "derive")]
+pub use advent_of_code_helpers_derive::LineParse;
@@ -1,3 +1,18 @@
+use crate::buf_line_iterator::BufLineIterator;
+use crate::columns;
+use crate::ints::{extract_ints, FromInts};
+use crate::sections::{FromSections, HeaderSectionIterator};
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, StdinLock};
+use std::iter::{FusedIterator, Map};
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Clone, Debug)]
 pub enum TrimMode {
     /// Trim the entire line
     All,
@@ -5,35 +20,298 @@ pub enum TrimMode {
     LineEndOnly,
     /// Do not trim the line
     None,
+    /// Trim the line ending, then any of these characters from both ends of the line
+    Chars(Vec<char>),
 }
 
+impl TrimMode {
+    /// Trim a single line, which may still include its line ending.
+    pub fn trim<'a>(&self, line: &'a str) -> &'a str {
+        match self {
+            TrimMode::All => line.trim(),
+            TrimMode::LineEndOnly => line.trim_end_matches(['\r', '\n']),
+            TrimMode::None => line,
+            TrimMode::Chars(chars) => line
+                .trim_end_matches(['\r', '\n'])
+                .trim_matches(chars.as_slice()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct LineIteratorSettings {
     pub trim_mode: TrimMode,
+    /// Skip lines that are empty or only whitespace after trimming
+    pub skip_empty: bool,
+    /// Skip lines starting with this prefix, ignoring leading whitespace, like `#` or `//`
+    pub comment_prefix: Option<String>,
+}
+
+impl LineIteratorSettings {
+    /// Trim a single line, or return `None` if it should be skipped.
+    pub fn apply<'a>(&self, line: &'a str) -> Option<&'a str> {
+        if let Some(prefix) = &self.comment_prefix {
+            if line.trim_start().starts_with(prefix.as_str()) {
+                return None;
+            }
+        }
+
+        let line = self.trim_mode.trim(line);
+
+        if self.skip_empty && line.trim().is_empty() {
+            return None;
+        }
+
+        Some(line)
+    }
+
+    /// Whether [`Self::apply`] may skip lines.
+    fn skips_lines(&self) -> bool {
+        self.skip_empty || self.comment_prefix.is_some()
+    }
 }
 
 impl Default for LineIteratorSettings {
     fn default() -> Self {
         Self {
             trim_mode: TrimMode::All,
+            skip_empty: false,
+            comment_prefix: None,
         }
     }
 }
 
+/// A line together with where it was found in the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Line<'a> {
+    /// The line after trimming, as [`LineIterator`] yields it
+    pub text: &'a str,
+    /// The line as it is in the input, including its line ending
+    pub raw: &'a str,
+    /// 0-based line number in the input, counting skipped lines
+    pub index: usize,
+    /// Byte range of `raw` in the input
+    pub span: Range<usize>,
+}
+
 pub struct LineIterator<'a> {
     input: &'a str,
     settings: LineIteratorSettings,
+    offset: usize,
+    index: usize,
+    /// Lines left in `input`, including ones the settings skip, counted on the first `next_back`
+    line_count: Option<usize>,
+    /// Lines left to yield, counted on the first call that needs it
+    remaining: Cell<Option<usize>>,
 }
 
 impl<'a> LineIterator<'a> {
     pub fn from(input: &'a str) -> Self {
+        Self::from_settings(input, LineIteratorSettings::default())
+    }
+
+    pub fn from_settings(input: &'a str, settings: LineIteratorSettings) -> Self {
+        Self::starting_at(input, settings, 0, 0)
+    }
+
+    /// Iterator over a part of a larger input, starting at byte `offset` and line `index`.
+    pub(crate) fn starting_at(
+        input: &'a str,
+        settings: LineIteratorSettings,
+        offset: usize,
+        index: usize,
+    ) -> Self {
         Self {
             input,
-            settings: LineIteratorSettings::default(),
+            settings,
+            offset,
+            index,
+            line_count: None,
+            remaining: Cell::new(None),
         }
     }
 
-    pub fn from_settings(input: &'a str, settings: LineIteratorSettings) -> Self {
-        Self { input, settings }
+    pub fn settings(&self) -> &LineIteratorSettings {
+        &self.settings
+    }
+
+    /// The input that has not been iterated over yet, untrimmed.
+    pub fn as_str(&self) -> &'a str {
+        self.input
+    }
+
+    /// Byte offset and line index of the remaining input in the original input.
+    pub(crate) fn position(&self) -> (usize, usize) {
+        (self.offset, self.index)
+    }
+
+    /// Read lines from a file instead of a string, see [`BufLineIterator`].
+    pub fn from_file<P>(path: P) -> io::Result<BufLineIterator<BufReader<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        BufLineIterator::from_file(path)
+    }
+
+    /// Read lines from stdin instead of a string, see [`BufLineIterator`].
+    pub fn from_stdin() -> BufLineIterator<StdinLock<'static>> {
+        BufLineIterator::from_stdin()
+    }
+
+    /// Pull all integers out of every line, see [`crate::ints::extract_ints`].
+    pub fn ints<T>(self) -> Map<Self, fn(&'a str) -> T>
+    where
+        T: FromInts,
+    {
+        self.map(extract_ints)
+    }
+
+    /// Parse the whitespace separated values of every line into one [`Vec`] per column, see
+    /// [`columns::columns`].
+    pub fn columns<T>(self) -> Vec<Vec<T>>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        columns::columns(self)
+    }
+
+    /// Parse columns of fixed character widths into one [`Vec`] per column, with `None` for empty
+    /// cells, see [`columns::fixed_width_columns`].
+    pub fn fixed_width_columns<T>(self, widths: &[usize]) -> Vec<Vec<Option<T>>>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        columns::fixed_width_columns(self, widths)
+    }
+
+    /// Split the remaining input into blank-line separated groups, keeping the current settings.
+    pub fn groups(self) -> GroupIterator<'a> {
+        GroupIterator {
+            input: self.input,
+            settings: self.settings,
+            offset: self.offset,
+            index: self.index,
+        }
+    }
+
+    /// Split the remaining input into groups that each start with a header line, like `Rules:`,
+    /// see [`HeaderSectionIterator`]. The header lines themselves are left out.
+    pub fn header_groups<F>(self, is_header: F) -> HeaderSectionIterator<'a, F>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        HeaderSectionIterator::from_line_iterator(self, is_header)
+    }
+
+    /// Parse the blank-line separated groups into a tuple with one element per group, like
+    /// `(Vec2D<char>, ParsingLineIterator<Move>)`, see [`crate::sections::FromSection`].
+    ///
+    /// Panics if the number of groups does not match the tuple.
+    pub fn sections<T>(self) -> T
+    where
+        T: FromSections<'a>,
+    {
+        T::from_sections(self.groups()).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`Self::sections`], but splitting into groups using [`Self::header_groups`].
+    pub fn sections_by_header<T, F>(self, is_header: F) -> T
+    where
+        T: FromSections<'a>,
+        F: FnMut(&str) -> bool,
+    {
+        T::from_sections(self.header_groups(is_header)).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Yield every line as a [`Line`], with its untrimmed text and position in the input.
+    pub fn with_positions(self) -> LinePositions<'a> {
+        LinePositions {
+            line_iterator: self,
+        }
+    }
+
+    /// Number of lines left to yield. The lines are counted on the first call, after that this
+    /// takes constant time.
+    pub fn remaining(&self) -> usize {
+        if let Some(remaining) = self.remaining.get() {
+            return remaining;
+        }
+
+        let remaining = count_kept_lines(self.input, &self.settings);
+        self.remaining.set(Some(remaining));
+
+        remaining
+    }
+
+    /// Keep the counts up to date after consuming a line.
+    fn consumed(&mut self, kept: bool) {
+        if let Some(line_count) = &mut self.line_count {
+            *line_count -= 1;
+        }
+
+        if kept {
+            self.remaining
+                .set(self.remaining.get().map(|remaining| remaining - 1));
+        }
+    }
+
+    pub(crate) fn next_line(&mut self) -> Option<Line<'a>> {
+        while !self.input.is_empty() {
+            let newline_position = next_line_end(self.input);
+
+            let (raw, rest) = self.input.split_at(newline_position);
+            let span = self.offset..self.offset + raw.len();
+            let index = self.index;
+
+            self.input = rest;
+            self.offset = span.end;
+            self.index += 1;
+
+            let text = self.settings.apply(raw);
+            self.consumed(text.is_some());
+
+            if let Some(text) = text {
+                return Some(Line {
+                    text,
+                    raw,
+                    index,
+                    span,
+                });
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn next_back_line(&mut self) -> Option<Line<'a>> {
+        while !self.input.is_empty() {
+            let start = previous_line_start(self.input);
+
+            let (rest, raw) = self.input.split_at(start);
+            let span = self.offset + start..self.offset + self.input.len();
+
+            let line_count = *self
+                .line_count
+                .get_or_insert_with(|| count_lines(self.input));
+
+            self.input = rest;
+
+            let text = self.settings.apply(raw);
+            self.consumed(text.is_some());
+
+            if let Some(text) = text {
+                return Some(Line {
+                    text,
+                    raw,
+                    index: self.index + line_count - 1,
+                    span,
+                });
+            }
+        }
+
+        None
     }
 }
 
@@ -41,23 +319,443 @@ impl<'a> Iterator for LineIterator<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.input.is_empty() {
-            return None;
+        self.next_line().map(|line| line.text)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for LineIterator<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_line().map(|line| line.text)
+    }
+}
+
+impl<'a> ExactSizeIterator for LineIterator<'a> {}
+
+impl<'a> FusedIterator for LineIterator<'a> {}
+
+/// Iterates over the lines of a [`LineIterator`] along with their positions, see
+/// [`LineIterator::with_positions`].
+pub struct LinePositions<'a> {
+    line_iterator: LineIterator<'a>,
+}
+
+impl<'a> Iterator for LinePositions<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line_iterator.next_line()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.line_iterator.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for LinePositions<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.line_iterator.next_back_line()
+    }
+}
+
+impl<'a> ExactSizeIterator for LinePositions<'a> {}
+
+impl<'a> FusedIterator for LinePositions<'a> {}
+
+/// Iterates over blocks of lines that are separated by one or more blank lines, yielding each
+/// block as its own [`LineIterator`].
+pub struct GroupIterator<'a> {
+    input: &'a str,
+    settings: LineIteratorSettings,
+    offset: usize,
+    index: usize,
+}
+
+impl<'a> GroupIterator<'a> {
+    pub fn from(input: &'a str) -> Self {
+        Self::from_settings(input, LineIteratorSettings::default())
+    }
+
+    pub fn from_settings(input: &'a str, settings: LineIteratorSettings) -> Self {
+        Self {
+            input,
+            settings,
+            offset: 0,
+            index: 0,
         }
+    }
 
-        let newline_position = self
-            .input
-            .find('\n')
-            .map(|i| i + 1)
-            .unwrap_or(self.input.len());
+    fn advance(&mut self, bytes: usize) {
+        let (skipped, rest) = self.input.split_at(bytes);
 
-        let (line, rest) = self.input.split_at(newline_position);
         self.input = rest;
+        self.offset += bytes;
+        self.index += skipped.matches('\n').count();
+    }
+}
+
+impl<'a> Iterator for GroupIterator<'a> {
+    type Item = LineIterator<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Skip any blank lines in front of the group
+            loop {
+                if self.input.is_empty() {
+                    return None;
+                }
+
+                let line_end = next_line_end(self.input);
+
+                if !self.input[..line_end].trim().is_empty() {
+                    break;
+                }
+
+                self.advance(line_end);
+            }
+
+            // Consume lines until we hit a blank one, or the end of the input
+            let mut group_end = 0;
+
+            while group_end < self.input.len() {
+                let line_end = group_end + next_line_end(&self.input[group_end..]);
+
+                if self.input[group_end..line_end].trim().is_empty() {
+                    break;
+                }
+
+                group_end = line_end;
+            }
+
+            let group = LineIterator::starting_at(
+                &self.input[..group_end],
+                self.settings.clone(),
+                self.offset,
+                self.index,
+            );
+            self.advance(group_end);
+
+            // A block of only comment lines separates groups like a blank line does
+            if !self.settings.skips_lines() || group.len() > 0 {
+                return Some(group);
+            }
+        }
+    }
+}
+
+/// Byte position just past the first line ending in the input, or the input length if there is none.
+#[inline]
+pub(crate) fn next_line_end(input: &str) -> usize {
+    input.find('\n').map(|i| i + 1).unwrap_or(input.len())
+}
+
+/// Byte position where the last line of a non-empty input starts, its line ending included.
+#[inline]
+fn previous_line_start(input: &str) -> usize {
+    let without_line_end = input.strip_suffix('\n').unwrap_or(input);
+
+    without_line_end.rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+/// Number of lines in the input that the settings do not skip.
+fn count_kept_lines(mut input: &str, settings: &LineIteratorSettings) -> usize {
+    if !settings.skips_lines() {
+        return count_lines(input);
+    }
+
+    let mut count = 0;
+
+    while !input.is_empty() {
+        let (raw, rest) = input.split_at(next_line_end(input));
+        input = rest;
+
+        if settings.apply(raw).is_some() {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+/// Number of lines [`next_line_end`] splits the input into.
+fn count_lines(input: &str) -> usize {
+    input.matches('\n').count() + usize::from(!input.is_empty() && !input.ends_with('\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_yields_positions() {
+        let input = "  a\r\n\n# comment\nb  ";
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            comment_prefix: Some("#".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![
+                Line {
+                    text: "a",
+                    raw: "  a\r\n",
+                    index: 0,
+                    span: 0..5,
+                },
+                Line {
+                    text: "b",
+                    raw: "b  ",
+                    index: 3,
+                    span: 16..19,
+                }
+            ],
+            LineIterator::from_settings(input, settings)
+                .with_positions()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_iterates_from_the_back() {
+        let input = "    [D]\r\n[N] [C]\r\n 1   2 \r\n";
+
+        let settings = LineIteratorSettings {
+            trim_mode: TrimMode::LineEndOnly,
+            ..Default::default()
+        };
+
+        let mut lines = LineIterator::from_settings(input, settings.clone());
+        assert_eq!(Some(" 1   2 "), lines.next_back());
+        assert_eq!(Some("    [D]"), lines.next());
+        assert_eq!(Some("[N] [C]"), lines.next_back());
+        assert_eq!(None, lines.next_back());
+        assert_eq!(None, lines.next());
+
+        for input in ["a\n  b \n\nc  ", "a\n\n", "\n", ""] {
+            let mut forward = LineIterator::from(input)
+                .with_positions()
+                .collect::<Vec<_>>();
+            forward.reverse();
+
+            assert_eq!(
+                forward,
+                LineIterator::from(input)
+                    .with_positions()
+                    .rev()
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn lines_are_only_counted_when_needed() {
+        let mut lines = LineIterator::from("a\nb\nc");
+
+        lines.next();
+        assert_eq!((None, None), (lines.line_count, lines.remaining.get()));
+
+        assert_eq!(2, lines.len());
+        lines.next();
+        assert_eq!(Some(1), lines.remaining.get());
+
+        assert_eq!(Some("c"), lines.next_back());
+        assert_eq!(
+            (Some(0), Some(0)),
+            (lines.line_count, lines.remaining.get())
+        );
+    }
+
+    #[test]
+    fn it_counts_the_remaining_lines() {
+        let mut lines = LineIterator::from("a\n\nb\nc");
+        assert_eq!(4, lines.len());
+
+        lines.next();
+        lines.next_back();
+        assert_eq!(2, lines.remaining());
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            ..Default::default()
+        };
+
+        let mut lines = LineIterator::from_settings("a\n\nb\nc\n\n", settings);
+        assert_eq!(3, lines.remaining());
+
+        lines.next_back();
+        assert_eq!(2, lines.len());
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            comment_prefix: Some("#".to_string()),
+            ..Default::default()
+        };
+
+        let mut lines = LineIterator::from_settings("# a\n\nb\n# c\nd\n\n", settings);
+        let mut yielded = 0;
+
+        while lines.len() > 0 {
+            assert_eq!(2 - yielded, lines.len());
+            lines.next();
+            yielded += 1;
+        }
+
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn it_skips_empty_lines() {
+        let input = "1\n\n2\n  \n3\n\n\n";
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec!["1", "2", "3"],
+            LineIterator::from_settings(input, settings).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_skips_comments() {
+        let input = "// fixture\na\n  // indented\nb // not a comment line\n";
+
+        let settings = LineIteratorSettings {
+            comment_prefix: Some("//".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec!["a", "b // not a comment line"],
+            LineIterator::from_settings(input, settings).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_trims_custom_characters() {
+        let input = "|a b|\r\n||c|\n";
+
+        let settings = LineIteratorSettings {
+            trim_mode: TrimMode::Chars(vec!['|', ' ']),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec!["a b", "c"],
+            LineIterator::from_settings(input, settings).collect::<Vec<_>>()
+        );
+    }
+
+    mod groups {
+        use super::*;
+
+        #[test]
+        fn it_splits_on_blank_lines() {
+            let input = "1000\n2000\n\n4000\n\n5000\n6000\n";
+
+            let groups = GroupIterator::from(input)
+                .map(|group| group.collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                vec![vec!["1000", "2000"], vec!["4000"], vec!["5000", "6000"]],
+                groups
+            );
+        }
+
+        #[test]
+        fn it_handles_crlf_line_endings() {
+            let input = "a\r\nb\r\n\r\nc\r\n";
+
+            let settings = LineIteratorSettings {
+                trim_mode: TrimMode::LineEndOnly,
+                ..Default::default()
+            };
+
+            let groups = GroupIterator::from_settings(input, settings)
+                .map(|group| group.collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            assert_eq!(vec![vec!["a", "b"], vec!["c"]], groups);
+        }
+
+        #[test]
+        fn it_ignores_leading_trailing_and_repeated_blank_lines() {
+            let input = "\n\nx\n\n\n\ny\nz\n\n\n";
+
+            let groups = LineIterator::from(input)
+                .groups()
+                .map(|group| group.collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            assert_eq!(vec![vec!["x"], vec!["y", "z"]], groups);
+        }
+
+        #[test]
+        fn groups_keep_positions_in_the_whole_input() {
+            let input = "a\n\n\nbb\ncc\n";
+
+            let positions = GroupIterator::from(input)
+                .map(|group| {
+                    group
+                        .with_positions()
+                        .map(|line| (line.index, line.span))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                vec![vec![(0, 0..2)], vec![(3, 4..7), (4, 7..10)]],
+                positions
+            );
+
+            for line in LineIterator::from(input)
+                .groups()
+                .flat_map(LineIterator::with_positions)
+            {
+                assert_eq!(line.raw, &input[line.span.clone()]);
+            }
+        }
+
+        #[test]
+        fn it_honors_the_trim_mode() {
+            let input = "  a \n b\n\nc";
+
+            let settings = LineIteratorSettings {
+                trim_mode: TrimMode::None,
+                ..Default::default()
+            };
+
+            let groups = GroupIterator::from_settings(input, settings)
+                .map(|group| group.collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            assert_eq!(vec![vec!["  a \n", " b\n"], vec!["c"]], groups);
+        }
+
+        #[test]
+        fn comment_only_blocks_separate_groups() {
+            let input = "a\n\n# c\n# d\n\nb\n# e";
+
+            let settings = LineIteratorSettings {
+                comment_prefix: Some("#".to_string()),
+                ..Default::default()
+            };
+
+            let groups = GroupIterator::from_settings(input, settings)
+                .map(|group| group.collect::<Vec<_>>())
+                .collect::<Vec<_>>();
 
-        match self.settings.trim_mode {
-            TrimMode::All => Some(line.trim()),
-            TrimMode::LineEndOnly => Some(line.trim_end_matches(['\r', '\n'])),
-            TrimMode::None => Some(line),
+            assert_eq!(vec![vec!["a"], vec!["b"]], groups);
         }
     }
 }
@@ -1,5 +1,8 @@
-use crate::line_iterator::{LineIterator, LineIteratorSettings};
+use crate::line_iterator::{Line, LineIterator, LineIteratorSettings, LinePositions};
+use crate::pattern::{FromCaptures, PatternLineIterator};
+use crate::split_parsing_iterator::SplitParsingIterator;
 use std::fmt::Debug;
+use std::iter::{FusedIterator, Map};
 use std::marker::PhantomData;
 use std::str::FromStr;
 
@@ -22,13 +25,78 @@ impl<'a, T> ParsingLineIterator<'a, T> {
             marker: Default::default(),
         }
     }
+
+    /// Number of lines left to parse, see [`LineIterator::remaining`].
+    pub fn remaining(&self) -> usize {
+        self.line_iterator.remaining()
+    }
+
+    /// Parse every line by splitting it on `separator`, see [`SplitParsingIterator`]. Call
+    /// [`SplitParsingIterator::split`] again for nested separators.
+    ///
+    /// Only the primitive types and `String` can be used for the parts directly. Any other
+    /// [`FromStr`] type, including those using `#[derive(LineParse)]`, has to be wrapped in
+    /// [`Parsed`](crate::pattern::Parsed), like `Vec<Parsed<Op>>`.
+    pub fn split(self, separator: &str) -> SplitParsingIterator<'a, T> {
+        SplitParsingIterator::from_line_iterator(self.line_iterator, vec![separator.to_string()])
+    }
+}
+
+impl<'a, T> ParsingLineIterator<'a, T>
+where
+    T: FromCaptures,
+{
+    /// Parse every line into a tuple using a scanf-style [`crate::pattern::Pattern`], like
+    /// `move {} from {} to {}`.
+    pub fn with_pattern(input: &'a str, pattern: &str) -> PatternLineIterator<'a, T> {
+        PatternLineIterator::from_settings(input, pattern, LineIteratorSettings::default())
+    }
+
+    pub fn with_pattern_settings(
+        input: &'a str,
+        pattern: &str,
+        settings: LineIteratorSettings,
+    ) -> PatternLineIterator<'a, T> {
+        PatternLineIterator::from_settings(input, pattern, settings)
+    }
+}
+
+type WithPositions<'a, T> = Map<LinePositions<'a>, fn(Line<'a>) -> (Line<'a>, T)>;
+
+impl<'a, T> ParsingLineIterator<'a, T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    /// Yield every parsed value along with the [`Line`] it was parsed from.
+    pub fn with_positions(self) -> WithPositions<'a, T> {
+        self.line_iterator.with_positions().map(|line| {
+            let value = parse_line(&line);
+
+            (line, value)
+        })
+    }
+}
+
+fn parse_line<T>(line: &Line) -> T
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    line.text.parse().unwrap_or_else(|error| {
+        panic!(
+            "failed to parse line {} {:?}: {error:?}",
+            line.index + 1,
+            line.text
+        )
+    })
 }
 
 impl<'a, T> From<LineIterator<'a>> for ParsingLineIterator<'a, T> {
     fn from(line_iterator: LineIterator<'a>) -> Self {
         Self {
             line_iterator,
-            marker: PhantomData::<T>::default(),
+            marker: PhantomData::<T>,
         }
     }
 }
@@ -41,14 +109,40 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(line) = self.line_iterator.next() {
-            return Some(line.parse::<T>().unwrap());
-        }
+        self.line_iterator.next_line().map(|line| parse_line(&line))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.line_iterator.size_hint()
+    }
+}
 
-        None
+impl<'a, T> DoubleEndedIterator for ParsingLineIterator<'a, T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.line_iterator
+            .next_back_line()
+            .map(|line| parse_line(&line))
     }
 }
 
+impl<'a, T> ExactSizeIterator for ParsingLineIterator<'a, T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+}
+
+impl<'a, T> FusedIterator for ParsingLineIterator<'a, T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,4 +221,70 @@ sub 12"#;
             ops
         )
     }
+
+    #[test]
+    fn it_applies_the_settings() {
+        let input = "# numbers\n1\n2\n\n\n";
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            comment_prefix: Some("#".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![1, 2],
+            ParsingLineIterator::<u8>::from_settings(input, settings).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_exposes_line_positions() {
+        let input = "1\n\n# skipped\n3";
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            comment_prefix: Some("#".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![(0, 0..2, 1), (3, 13..14, 3)],
+            ParsingLineIterator::<u8>::from_settings(input, settings)
+                .with_positions()
+                .map(|(line, value)| (line.index, line.span, value))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse line 3 \"x\"")]
+    fn it_panics_with_the_line_number() {
+        ParsingLineIterator::<u8>::from("1\n2\nx").for_each(drop);
+    }
+
+    #[test]
+    fn it_parses_from_the_back() {
+        let mut values = ParsingLineIterator::<i32>::from("1\n2\n3\n4\n");
+        assert_eq!(4, values.remaining());
+
+        assert_eq!(Some(4), values.next_back());
+        assert_eq!(Some(1), values.next());
+        assert_eq!(2, values.len());
+        assert_eq!(vec![3, 2], values.rev().collect::<Vec<_>>());
+
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![(3, 30), (0, 10)],
+            ParsingLineIterator::<i32>::from_settings("10\n\n\n30", settings)
+                .with_positions()
+                .rev()
+                .map(|(line, value)| (line.index, value))
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,613 @@
+use crate::vec2d::{Vec2D, NEIGHBOURS4};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Breadth-first search over the 4 orthogonal neighbours, returning the amount of steps needed to
+/// reach every cell, `None` for cells that cannot be reached.
+///
+/// The start is always reached, other cells only if they are `passable`.
+///
+/// Panics if the start lies outside of the grid.
+pub fn bfs<T, P>(grid: &Vec2D<T>, start: (isize, isize), passable: P) -> Vec2D<Option<usize>>
+where
+    P: FnMut(&T) -> bool,
+{
+    bfs_multi_source(grid, [start], passable)
+}
+
+/// Like [`bfs`], but starting from several cells at once, so every cell gets the distance to the
+/// nearest start.
+///
+/// Panics if any of the starts lies outside of the grid.
+pub fn bfs_multi_source<T, P, I>(grid: &Vec2D<T>, starts: I, passable: P) -> Vec2D<Option<usize>>
+where
+    P: FnMut(&T) -> bool,
+    I: IntoIterator<Item = (isize, isize)>,
+{
+    search(grid, starts, None, passable).0
+}
+
+/// The shortest path from `start` to `target` over passable cells, including both ends.
+///
+/// Panics if the start lies outside of the grid.
+pub fn shortest_path<T, P>(
+    grid: &Vec2D<T>,
+    start: (isize, isize),
+    target: (isize, isize),
+    passable: P,
+) -> Option<Vec<(isize, isize)>>
+where
+    P: FnMut(&T) -> bool,
+{
+    let (distances, previous) = search(grid, [start], Some(target), passable);
+
+    distances.at(target.0, target.1).copied().flatten()?;
+
+    let mut path = vec![target];
+    let mut current = target;
+
+    while let Some(&Some(position)) = previous.at(current.0, current.1) {
+        path.push(position);
+        current = position;
+    }
+
+    path.reverse();
+
+    Some(path)
+}
+
+/// Result of [`dijkstra`] or [`astar`], holding every optimal way of reaching the goal.
+#[derive(Debug)]
+pub struct ShortestPaths<S> {
+    cost: usize,
+    states: Vec<S>,
+    goals: Vec<usize>,
+    /// For every state the states it can be reached from at its lowest cost
+    previous: Vec<Vec<usize>>,
+}
+
+impl<S> ShortestPaths<S>
+where
+    S: Clone,
+{
+    /// The total cost of the optimal paths.
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    /// The goal states that were reached at the optimal cost.
+    pub fn goals(&self) -> Vec<S> {
+        self.goals.iter().map(|&i| self.states[i].clone()).collect()
+    }
+
+    /// One of the optimal paths, from start to goal.
+    pub fn path(&self) -> Vec<S> {
+        let mut path = vec![self.goals[0]];
+
+        while let Some(&previous) = self.previous[*path.last().unwrap()].first() {
+            path.push(previous);
+        }
+
+        path.into_iter()
+            .rev()
+            .map(|i| self.states[i].clone())
+            .collect()
+    }
+
+    /// Every optimal path, from start to goal. The amount of paths can grow exponentially, see
+    /// [`ShortestPaths::states_on_paths`] when only the states matter.
+    pub fn all_paths(&self) -> Vec<Vec<S>> {
+        let mut paths = Vec::new();
+        let mut stack = self
+            .goals
+            .iter()
+            .map(|&goal| vec![goal])
+            .collect::<Vec<_>>();
+
+        while let Some(path) = stack.pop() {
+            let previous = &self.previous[*path.last().unwrap()];
+
+            if previous.is_empty() {
+                paths.push(path.iter().rev().map(|&i| self.states[i].clone()).collect());
+                continue;
+            }
+
+            for &state in previous {
+                let mut path = path.clone();
+                path.push(state);
+                stack.push(path);
+            }
+        }
+
+        paths
+    }
+
+    /// Every state that lies on at least one of the optimal paths.
+    pub fn states_on_paths(&self) -> HashSet<S>
+    where
+        S: Eq + Hash,
+    {
+        let mut seen = vec![false; self.states.len()];
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            if std::mem::replace(&mut seen[state], true) {
+                continue;
+            }
+
+            stack.extend(&self.previous[state]);
+        }
+
+        seen.iter()
+            .enumerate()
+            .filter(|(_, seen)| **seen)
+            .map(|(i, _)| self.states[i].clone())
+            .collect()
+    }
+}
+
+/// Dijkstra's algorithm over any state space. `successors` yields the states reachable from a
+/// state together with the cost of getting there. Returns `None` if no goal can be reached.
+pub fn dijkstra<S, FS, I, FG>(
+    starts: impl IntoIterator<Item = S>,
+    successors: FS,
+    is_goal: FG,
+) -> Option<ShortestPaths<S>>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// A* search over any state space, like [`dijkstra`] but guided by a `heuristic` estimating the
+/// remaining cost to a goal. The heuristic must never overestimate, or the result may not be
+/// optimal.
+pub fn astar<S, FS, I, FH, FG>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: FS,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<ShortestPaths<S>>
+where
+    S: Clone + Eq + Hash,
+    FS: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    FH: FnMut(&S) -> usize,
+    FG: FnMut(&S) -> bool,
+{
+    // States are referred to by their index, so they do not need to be ordered
+    let mut states = Vec::new();
+    let mut indices = HashMap::new();
+    let mut costs = Vec::new();
+    let mut previous: Vec<Vec<usize>> = Vec::new();
+    let mut done = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    let mut intern = |state: S, states: &mut Vec<S>| -> (usize, bool) {
+        if let Some(&index) = indices.get(&state) {
+            return (index, false);
+        }
+
+        indices.insert(state.clone(), states.len());
+        states.push(state);
+
+        (states.len() - 1, true)
+    };
+
+    for start in starts {
+        let (index, new) = intern(start, &mut states);
+
+        if new {
+            costs.push(0);
+            previous.push(Vec::new());
+            done.push(false);
+            heap.push(Reverse((heuristic(&states[index]), 0, index)));
+        }
+    }
+
+    let mut best = None;
+    let mut goals = Vec::new();
+
+    while let Some(Reverse((estimate, cost, index))) = heap.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+
+        if done[index] || cost > costs[index] {
+            continue;
+        }
+
+        done[index] = true;
+
+        if is_goal(&states[index]) {
+            best = Some(cost);
+            goals.push(index);
+            continue;
+        }
+
+        for (next, step) in successors(&states[index]) {
+            let next_cost = cost + step;
+            let (next_index, new) = intern(next, &mut states);
+
+            if new {
+                costs.push(next_cost);
+                previous.push(vec![index]);
+                done.push(false);
+            } else if next_cost < costs[next_index] {
+                // An inconsistent heuristic can find a cheaper route to a finished state, so
+                // reopen it
+                costs[next_index] = next_cost;
+                previous[next_index] = vec![index];
+                done[next_index] = false;
+            } else if next_cost == costs[next_index] && !done[next_index] {
+                previous[next_index].push(index);
+                continue;
+            } else {
+                continue;
+            }
+
+            heap.push(Reverse((
+                next_cost + heuristic(&states[next_index]),
+                next_cost,
+                next_index,
+            )));
+        }
+    }
+
+    Some(ShortestPaths {
+        cost: best?,
+        states,
+        goals,
+        previous,
+    })
+}
+
+/// A neighbouring cell and the cost of entering it.
+type CellStep = ((isize, isize), usize);
+
+/// Successor function for [`dijkstra`] and [`astar`] moving between orthogonal neighbours on the
+/// grid. `cost` gives the cost of entering a cell, `None` if it cannot be entered.
+pub fn grid_successors<'a, T, C>(
+    grid: &'a Vec2D<T>,
+    mut cost: C,
+) -> impl FnMut(&(isize, isize)) -> Vec<CellStep> + 'a
+where
+    C: FnMut(&T) -> Option<usize> + 'a,
+{
+    move |&(row, column)| {
+        grid.neighbours_with(row, column, &NEIGHBOURS4)
+            .filter_map(|(row, column, cell)| cost(cell).map(|cost| ((row, column), cost)))
+            .collect()
+    }
+}
+
+/// Heuristic for [`astar`] on a grid, the Manhattan distance to the target. This only holds while
+/// entering any cell costs at least 1.
+pub fn manhattan_heuristic(target: (isize, isize)) -> impl Fn(&(isize, isize)) -> usize {
+    move |&(row, column)| row.abs_diff(target.0) + column.abs_diff(target.1)
+}
+
+/// The cheapest paths between two cells of the grid, where `cost` gives the cost of entering a cell
+/// or `None` if it cannot be entered.
+pub fn grid_shortest_paths<T, C>(
+    grid: &Vec2D<T>,
+    start: (isize, isize),
+    target: (isize, isize),
+    cost: C,
+) -> Option<ShortestPaths<(isize, isize)>>
+where
+    C: FnMut(&T) -> Option<usize>,
+{
+    dijkstra([start], grid_successors(grid, cost), |&position| {
+        position == target
+    })
+}
+
+/// For every cell the cell it was first reached from.
+type Previous = Vec2D<Option<(isize, isize)>>;
+
+/// Run the search, stopping early once the target is reached.
+fn search<T, P, I>(
+    grid: &Vec2D<T>,
+    starts: I,
+    target: Option<(isize, isize)>,
+    mut passable: P,
+) -> (Vec2D<Option<usize>>, Previous)
+where
+    P: FnMut(&T) -> bool,
+    I: IntoIterator<Item = (isize, isize)>,
+{
+    let mut distances = Vec2D::new_sized_like(grid, None);
+    let mut previous = Vec2D::new_sized_like(grid, None);
+    let mut queue = VecDeque::new();
+
+    for (row, column) in starts {
+        let distance = distances.at_mut(row, column).unwrap_or_else(|| {
+            panic!("start (row {row}, column {column}) lies outside of the grid")
+        });
+
+        if distance.is_none() {
+            *distance = Some(0);
+            queue.push_back((row, column, 0));
+        }
+    }
+
+    while let Some((row, column, distance)) = queue.pop_front() {
+        if target == Some((row, column)) {
+            break;
+        }
+
+        for (next_row, next_column, cell) in grid.neighbours4(row, column) {
+            let next_distance = distances.at_mut_unchecked(next_row, next_column);
+
+            if next_distance.is_some() || !passable(cell) {
+                continue;
+            }
+
+            *next_distance = Some(distance + 1);
+            *previous.at_mut_unchecked(next_row, next_column) = Some((row, column));
+            queue.push_back((next_row, next_column, distance + 1));
+        }
+    }
+
+    (distances, previous)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bfs_computes_distances() {
+        let grid: Vec2D<char> = "\
+S.#.
+..#.
+#...
+...E"
+            .parse()
+            .unwrap();
+
+        let distances = bfs(&grid, (0, 0), |c| *c != '#');
+
+        assert_eq!(Some(&Some(0)), distances.at(0, 0));
+        assert_eq!(Some(&Some(6)), distances.at(3, 3));
+        assert_eq!(Some(&Some(7)), distances.at(0, 3));
+        assert_eq!(Some(&None), distances.at(0, 2));
+    }
+
+    #[test]
+    fn bfs_leaves_unreachable_cells_empty() {
+        let grid: Vec2D<char> = ".#.\n.#.".parse().unwrap();
+
+        let distances = bfs(&grid, (0, 0), |c| *c == '.');
+
+        assert_eq!(Some(&Some(1)), distances.at(1, 0));
+        assert_eq!(Some(&None), distances.at(0, 2));
+    }
+
+    #[test]
+    fn bfs_multi_source_uses_the_nearest_start() {
+        let grid: Vec2D<char> = ".....".parse().unwrap();
+
+        let distances = bfs_multi_source(&grid, [(0, 0), (0, 4)], |_| true);
+
+        assert_eq!(
+            vec![Some(0), Some(1), Some(2), Some(1), Some(0)],
+            distances.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn shortest_path_works() {
+        let grid: Vec2D<char> = "\
+S.#.
+..#.
+#...
+...E"
+            .parse()
+            .unwrap();
+
+        let path = shortest_path(&grid, (0, 0), (3, 3), |c| *c != '#').unwrap();
+
+        assert_eq!(7, path.len());
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(3, 3)), path.last());
+
+        for step in path.windows(2) {
+            let ((a_row, a_column), (b_row, b_column)) = (step[0], step[1]);
+            assert_eq!(1, a_row.abs_diff(b_row) + a_column.abs_diff(b_column));
+        }
+
+        assert_eq!(None, shortest_path(&grid, (0, 0), (0, 2), |c| *c != '#'));
+    }
+
+    #[test]
+    fn it_respects_negative_coordinates() {
+        let grid = Vec2D::from_negative(
+            vec![
+                '.', '.', '#', // Row -1
+                '#', '.', '.', // Row 0
+            ],
+            2,
+            1,
+            1,
+            1,
+        );
+
+        let distances = bfs(&grid, (-1, -1), |c| *c == '.');
+        assert_eq!(Some(&Some(3)), distances.at(0, 1));
+
+        assert_eq!(
+            Some(vec![(-1, -1), (-1, 0), (0, 0), (0, 1)]),
+            shortest_path(&grid, (-1, -1), (0, 1), |c| *c == '.')
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "start (row 4, column 0) lies outside of the grid")]
+    fn bfs_panics_on_a_start_outside_of_the_grid() {
+        let grid: Vec2D<char> = "..\n..".parse().unwrap();
+
+        bfs(&grid, (4, 0), |c| *c == '.');
+    }
+
+    mod weighted {
+        use super::*;
+
+        #[test]
+        fn grid_shortest_paths_works() {
+            let grid: Vec2D<u32> = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+                .parse()
+                .unwrap();
+
+            let paths =
+                grid_shortest_paths(&grid, (0, 0), (9, 9), |risk| Some(*risk as usize)).unwrap();
+
+            assert_eq!(40, paths.cost());
+
+            let path = paths.path();
+            assert_eq!(Some(&(0, 0)), path.first());
+            assert_eq!(Some(&(9, 9)), path.last());
+        }
+
+        #[test]
+        fn astar_agrees_with_dijkstra() {
+            let grid: Vec2D<u32> = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+                .parse()
+                .unwrap();
+
+            let paths = astar(
+                [(0, 0)],
+                grid_successors(&grid, |risk| Some(*risk as usize)),
+                manhattan_heuristic((9, 9)),
+                |&position| position == (9, 9),
+            )
+            .unwrap();
+
+            assert_eq!(40, paths.cost());
+        }
+
+        #[test]
+        fn astar_reopens_states_with_an_inconsistent_heuristic() {
+            let edges = HashMap::from([
+                ('S', vec![('A', 1), ('C', 3)]),
+                ('A', vec![('C', 1)]),
+                ('C', vec![('G', 3)]),
+                ('G', vec![]),
+            ]);
+
+            let paths = astar(
+                ['S'],
+                |state| edges[state].clone(),
+                |&state| if state == 'A' { 4 } else { 0 },
+                |&state| state == 'G',
+            )
+            .unwrap();
+
+            assert_eq!(5, paths.cost());
+            assert_eq!(vec!['S', 'A', 'C', 'G'], paths.path());
+        }
+
+        #[test]
+        fn it_returns_none_without_a_path() {
+            let grid: Vec2D<char> = "..#.".parse().unwrap();
+
+            assert!(
+                grid_shortest_paths(&grid, (0, 0), (0, 3), |c| (*c == '.').then_some(1)).is_none()
+            );
+        }
+
+        #[test]
+        fn it_recovers_all_optimal_paths() {
+            let grid: Vec2D<char> = "...\n.#.\n...".parse().unwrap();
+
+            let paths =
+                grid_shortest_paths(&grid, (0, 0), (2, 2), |c| (*c == '.').then_some(1)).unwrap();
+
+            assert_eq!(4, paths.cost());
+
+            let mut all_paths = paths.all_paths();
+            all_paths.sort();
+            assert_eq!(
+                vec![
+                    vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
+                    vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+                ],
+                all_paths
+            );
+
+            assert_eq!(8, paths.states_on_paths().len());
+        }
+
+        #[test]
+        fn it_searches_custom_states() {
+            // Crucible that has to move at least 4 and at most 10 steps in a straight line
+            let grid: Vec2D<u32> = "\
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991"
+                .parse()
+                .unwrap();
+
+            type State = ((isize, isize), (isize, isize), usize);
+
+            let successors = |&((row, column), direction, steps): &State| {
+                let mut next = Vec::new();
+
+                for turn in NEIGHBOURS4 {
+                    let straight = turn == direction;
+
+                    if turn == (-direction.0, -direction.1)
+                        || (straight && steps == 10)
+                        || (!straight && steps < 4 && direction != (0, 0))
+                    {
+                        continue;
+                    }
+
+                    let position = (row + turn.0, column + turn.1);
+                    if let Some(cost) = grid.at(position.0, position.1) {
+                        let steps = if straight { steps + 1 } else { 1 };
+                        next.push(((position, turn, steps), *cost as usize));
+                    }
+                }
+
+                next
+            };
+
+            let paths = dijkstra(
+                [((0, 0), (0, 0), 0)],
+                successors,
+                |&(position, _, steps)| position == (4, 11) && steps >= 4,
+            )
+            .unwrap();
+
+            assert_eq!(71, paths.cost());
+        }
+    }
+}
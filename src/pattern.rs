@@ -0,0 +1,787 @@
+use crate::line_iterator::{LineIterator, LineIteratorSettings};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// A scanf-style line pattern like `move {} from {} to {}`.
+///
+/// Placeholders:
+/// - `{}` a required field
+/// - `{?}` an optional field, which may be empty
+/// - `{*}` a field repeated any amount of times, separated by whitespace
+/// - `{*SEP}` a field repeated any amount of times, separated by `SEP`, like `{*,}`
+///
+/// Use `{{` and `}}` for literal braces. Any run of whitespace in the pattern matches any run of
+/// whitespace in the line, and placeholders must be separated by a literal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    segments: Vec<Segment>,
+    field_count: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Segment {
+    Literal(Vec<LiteralToken>),
+    Field(FieldKind),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum LiteralToken {
+    Text(String),
+    Whitespace,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FieldKind {
+    Required,
+    Optional,
+    /// Repeated with the given separator, `None` for whitespace
+    Repeated(Option<String>),
+}
+
+/// The text matched by a single placeholder.
+#[derive(Clone, Copy, Debug)]
+pub struct Capture<'a> {
+    pub text: &'a str,
+    pub kind: &'a FieldKind,
+}
+
+impl Pattern {
+    /// Compile the pattern.
+    ///
+    /// Panics if the pattern is invalid, see [`Pattern::try_new`].
+    pub fn new(pattern: &str) -> Self {
+        Self::try_new(pattern).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Compile the pattern, failing if it has unbalanced braces, an unknown placeholder, or two
+    /// placeholders without a literal in between.
+    pub fn try_new(pattern: &str) -> Result<Self, PatternSyntaxError> {
+        let error = |kind| PatternSyntaxError {
+            pattern: pattern.to_string(),
+            kind,
+        };
+        let mut segments = Vec::new();
+        let mut literal = Vec::new();
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    push_text(&mut literal, '{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    push_text(&mut literal, '}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err(error(PatternSyntaxErrorKind::UnclosedPlaceholder)),
+                        }
+                    }
+
+                    let kind = match placeholder.as_str() {
+                        "" => FieldKind::Required,
+                        "?" => FieldKind::Optional,
+                        "*" => FieldKind::Repeated(None),
+                        _ => match placeholder.strip_prefix('*') {
+                            Some(separator) => FieldKind::Repeated(Some(separator.to_string())),
+                            None => {
+                                return Err(error(PatternSyntaxErrorKind::UnknownPlaceholder {
+                                    placeholder,
+                                }))
+                            }
+                        },
+                    };
+
+                    if literal.is_empty() && matches!(segments.last(), Some(Segment::Field(_))) {
+                        return Err(error(PatternSyntaxErrorKind::AdjacentPlaceholders));
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    segments.push(Segment::Field(kind));
+                }
+                '}' => return Err(error(PatternSyntaxErrorKind::UnmatchedBrace)),
+                c if c.is_whitespace() => {
+                    if literal.last() != Some(&LiteralToken::Whitespace) {
+                        literal.push(LiteralToken::Whitespace);
+                    }
+                }
+                c => push_text(&mut literal, c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        // Lines are trimmed before matching, so whitespace around the pattern can never match
+        if let Some(Segment::Literal(tokens)) = segments.first_mut() {
+            if tokens.first() == Some(&LiteralToken::Whitespace) {
+                tokens.remove(0);
+            }
+        }
+
+        if let Some(Segment::Literal(tokens)) = segments.last_mut() {
+            if tokens.last() == Some(&LiteralToken::Whitespace) {
+                tokens.pop();
+            }
+        }
+
+        segments.retain(|segment| segment != &Segment::Literal(Vec::new()));
+
+        let field_count = segments
+            .iter()
+            .filter(|segment| matches!(segment, Segment::Field(_)))
+            .count();
+
+        Ok(Self {
+            segments,
+            field_count,
+        })
+    }
+
+    /// The amount of placeholders in the pattern.
+    pub fn field_count(&self) -> usize {
+        self.field_count
+    }
+
+    /// Match a line against the pattern, returning the text of every placeholder.
+    pub fn captures<'a>(&'a self, line: &'a str) -> Result<Vec<Capture<'a>>, PatternErrorKind> {
+        let line = line.trim();
+        let mut captures = Vec::with_capacity(self.field_count);
+        let mut position = 0;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Literal(tokens) => {
+                    position = match_literal(tokens, line, position).ok_or_else(|| {
+                        PatternErrorKind::LiteralMismatch {
+                            expected: literal_to_string(tokens),
+                            position,
+                        }
+                    })?;
+                }
+                Segment::Field(kind) => {
+                    let end = match self.segments.get(i + 1) {
+                        Some(Segment::Literal(tokens)) => (position..=line.len())
+                            .filter(|&end| line.is_char_boundary(end))
+                            .find(|&end| match_literal(tokens, line, end).is_some())
+                            .ok_or_else(|| PatternErrorKind::LiteralMismatch {
+                                expected: literal_to_string(tokens),
+                                position,
+                            })?,
+                        _ => line.len(),
+                    };
+
+                    let text = line[position..end].trim();
+
+                    if text.is_empty() && *kind == FieldKind::Required {
+                        return Err(PatternErrorKind::MissingField {
+                            placeholder: captures.len(),
+                        });
+                    }
+
+                    captures.push(Capture { text, kind });
+                    position = end;
+                }
+            }
+        }
+
+        if position < line.len() {
+            return Err(PatternErrorKind::TrailingText {
+                text: line[position..].to_string(),
+            });
+        }
+
+        Ok(captures)
+    }
+
+    /// Match a line against the pattern and convert the placeholders into a tuple.
+    pub fn parse<T>(&self, line: &str) -> Result<T, PatternErrorKind>
+    where
+        T: FromCaptures,
+    {
+        if self.field_count != T::COUNT {
+            return Err(PatternErrorKind::FieldCount {
+                expected: T::COUNT,
+                found: self.field_count,
+            });
+        }
+
+        T::from_captures(&self.captures(line)?)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternSyntaxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_new(s)
+    }
+}
+
+fn push_text(literal: &mut Vec<LiteralToken>, c: char) {
+    match literal.last_mut() {
+        Some(LiteralToken::Text(text)) => text.push(c),
+        _ => literal.push(LiteralToken::Text(c.to_string())),
+    }
+}
+
+fn literal_to_string(tokens: &[LiteralToken]) -> String {
+    tokens
+        .iter()
+        .map(|token| match token {
+            LiteralToken::Text(text) => text.as_str(),
+            LiteralToken::Whitespace => " ",
+        })
+        .collect()
+}
+
+/// Try to match the literal at the position, returning where it ends.
+fn match_literal(tokens: &[LiteralToken], line: &str, position: usize) -> Option<usize> {
+    let mut position = position;
+
+    for token in tokens {
+        let rest = &line[position..];
+
+        match token {
+            LiteralToken::Text(text) => {
+                if !rest.starts_with(text.as_str()) {
+                    return None;
+                }
+
+                position += text.len();
+            }
+            LiteralToken::Whitespace => {
+                let trimmed = rest.trim_start();
+
+                // Nothing to consume is fine if the whitespace was already consumed before, or was
+                // trimmed off the end of the line
+                if trimmed.len() == rest.len()
+                    && !rest.is_empty()
+                    && !line[..position].ends_with(char::is_whitespace)
+                {
+                    return None;
+                }
+
+                position += rest.len() - trimmed.len();
+            }
+        }
+    }
+
+    Some(position)
+}
+
+/// Why a pattern could not be compiled.
+#[derive(Debug, Eq, PartialEq)]
+pub enum PatternSyntaxErrorKind {
+    /// A `{` without a closing `}`
+    UnclosedPlaceholder,
+    /// A `}` without an opening `{`
+    UnmatchedBrace,
+    /// The text between the braces is not a known placeholder
+    UnknownPlaceholder { placeholder: String },
+    /// Two placeholders without a literal in between
+    AdjacentPlaceholders,
+}
+
+impl Display for PatternSyntaxErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternSyntaxErrorKind::UnclosedPlaceholder => write!(f, "unclosed placeholder"),
+            PatternSyntaxErrorKind::UnmatchedBrace => write!(f, "unmatched }}"),
+            PatternSyntaxErrorKind::UnknownPlaceholder { placeholder } => {
+                write!(f, "unknown placeholder {{{placeholder}}}")
+            }
+            PatternSyntaxErrorKind::AdjacentPlaceholders => {
+                write!(f, "placeholders must be separated by a literal")
+            }
+        }
+    }
+}
+
+/// A [`Pattern`] that could not be compiled.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PatternSyntaxError {
+    pub pattern: String,
+    pub kind: PatternSyntaxErrorKind,
+}
+
+impl Display for PatternSyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in pattern {:?}", self.kind, self.pattern)
+    }
+}
+
+impl Error for PatternSyntaxError {}
+
+/// Why a line did not match a [`Pattern`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum PatternErrorKind {
+    /// The literal text was not found at the byte position
+    LiteralMismatch { expected: String, position: usize },
+    /// A required placeholder (0-based) was empty
+    MissingField { placeholder: usize },
+    /// The text of a placeholder (0-based) could not be converted
+    InvalidField {
+        placeholder: usize,
+        text: String,
+        message: String,
+    },
+    /// The line continued after the end of the pattern
+    TrailingText { text: String },
+    /// The pattern has a different amount of placeholders than values are expected
+    FieldCount { expected: usize, found: usize },
+}
+
+impl Display for PatternErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternErrorKind::LiteralMismatch { expected, position } => {
+                write!(f, "expected {expected:?} after byte {position}")
+            }
+            PatternErrorKind::MissingField { placeholder } => {
+                write!(f, "placeholder {placeholder} is empty")
+            }
+            PatternErrorKind::InvalidField {
+                placeholder,
+                text,
+                message,
+            } => write!(
+                f,
+                "placeholder {placeholder} ({text:?}) is invalid: {message}"
+            ),
+            PatternErrorKind::TrailingText { text } => {
+                write!(f, "unexpected {text:?} after the end of the pattern")
+            }
+            PatternErrorKind::FieldCount { expected, found } => write!(
+                f,
+                "the pattern has {found} placeholders, but {expected} values are expected"
+            ),
+        }
+    }
+}
+
+/// A line that did not match a [`Pattern`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct PatternError {
+    /// 1-based line number
+    pub line_number: usize,
+    pub line: String,
+    pub kind: PatternErrorKind,
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} {:?}: {}",
+            self.line_number, self.line, self.kind
+        )
+    }
+}
+
+impl Error for PatternError {}
+
+/// Conversion from the text of a single placeholder.
+pub trait FromCapture: Sized {
+    fn from_capture(capture: Capture) -> Result<Self, String>;
+}
+
+macro_rules! impl_from_capture {
+    ($($t:ty),*) => {
+        $(
+            impl FromCapture for $t {
+                fn from_capture(capture: Capture) -> Result<Self, String> {
+                    capture.text.parse().map_err(|error| format!("{error:?}"))
+                }
+            }
+        )*
+    };
+}
+
+/// Invoke `$impl_macro` with the primitive types and `String`, the values parsed directly using
+/// their [`FromStr`] implementation.
+macro_rules! for_each_leaf_type {
+    ($impl_macro:ident) => {
+        $impl_macro!(
+            i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char,
+            String
+        );
+    };
+}
+
+pub(crate) use for_each_leaf_type;
+
+for_each_leaf_type!(impl_from_capture);
+
+/// Wrapper for using any [`FromStr`] type as a placeholder.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Parsed<T>(pub T);
+
+impl<T> FromCapture for Parsed<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    fn from_capture(capture: Capture) -> Result<Self, String> {
+        capture
+            .text
+            .parse()
+            .map(Parsed)
+            .map_err(|error| format!("{error:?}"))
+    }
+}
+
+impl<T> FromCapture for Option<T>
+where
+    T: FromCapture,
+{
+    fn from_capture(capture: Capture) -> Result<Self, String> {
+        if capture.text.is_empty() {
+            return Ok(None);
+        }
+
+        T::from_capture(capture).map(Some)
+    }
+}
+
+impl<T> FromCapture for Vec<T>
+where
+    T: FromCapture,
+{
+    fn from_capture(capture: Capture) -> Result<Self, String> {
+        let items: Box<dyn Iterator<Item = &str>> = match capture.kind {
+            FieldKind::Repeated(Some(separator)) => {
+                Box::new(capture.text.split(separator.as_str()).map(str::trim))
+            }
+            _ => Box::new(capture.text.split_whitespace()),
+        };
+
+        items
+            .filter(|text| !text.is_empty())
+            .map(|text| {
+                T::from_capture(Capture {
+                    text,
+                    kind: &FieldKind::Required,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Conversion from all placeholders of a line, implemented for tuples of [`FromCapture`] types.
+pub trait FromCaptures: Sized {
+    const COUNT: usize;
+
+    fn from_captures(captures: &[Capture]) -> Result<Self, PatternErrorKind>;
+}
+
+macro_rules! impl_from_captures {
+    ($count:expr; $($t:ident $i:tt),*) => {
+        impl<$($t),*> FromCaptures for ($($t,)*)
+        where
+            $($t: FromCapture),*
+        {
+            const COUNT: usize = $count;
+
+            fn from_captures(captures: &[Capture]) -> Result<Self, PatternErrorKind> {
+                if captures.len() != $count {
+                    return Err(PatternErrorKind::FieldCount {
+                        expected: $count,
+                        found: captures.len(),
+                    });
+                }
+
+                Ok(($(
+                    $t::from_capture(captures[$i]).map_err(|message| {
+                        PatternErrorKind::InvalidField {
+                            placeholder: $i,
+                            text: captures[$i].text.to_string(),
+                            message,
+                        }
+                    })?,
+                )*))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1; A 0);
+impl_from_captures!(2; A 0, B 1);
+impl_from_captures!(3; A 0, B 1, C 2);
+impl_from_captures!(4; A 0, B 1, C 2, D 3);
+impl_from_captures!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_captures!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_captures!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_from_captures!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_from_captures!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+
+/// Parses every line using a [`Pattern`], see
+/// [`crate::parsing_line_iterator::ParsingLineIterator::with_pattern`].
+pub struct PatternLineIterator<'a, T> {
+    line_iterator: LineIterator<'a>,
+    pattern: Pattern,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> PatternLineIterator<'a, T>
+where
+    T: FromCaptures,
+{
+    /// Panics if the pattern is invalid or its amount of placeholders does not match the tuple.
+    pub fn from_settings(input: &'a str, pattern: &str, settings: LineIteratorSettings) -> Self {
+        let pattern = Pattern::new(pattern);
+
+        assert_eq!(
+            T::COUNT,
+            pattern.field_count(),
+            "the pattern has {} placeholders, but {} values are expected",
+            pattern.field_count(),
+            T::COUNT
+        );
+
+        Self {
+            line_iterator: LineIterator::from_settings(input, settings),
+            pattern,
+            marker: PhantomData,
+        }
+    }
+
+    /// Parse the next line, returning an error describing the mismatch instead of panicking.
+    pub fn try_next(&mut self) -> Option<Result<T, PatternError>> {
+        let line = self.line_iterator.next_line()?;
+
+        Some(self.pattern.parse(line.text).map_err(|kind| PatternError {
+            line_number: line.index + 1,
+            line: line.text.to_string(),
+            kind,
+        }))
+    }
+}
+
+impl<'a, T> Iterator for PatternLineIterator<'a, T>
+where
+    T: FromCaptures,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next()
+            .map(|result| result.unwrap_or_else(|error| panic!("{error}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing_line_iterator::ParsingLineIterator;
+
+    #[test]
+    fn it_parses_simple_patterns() {
+        let input = "move 3 from 5 to 2\nmove 10 from 1 to 9";
+
+        let moves = ParsingLineIterator::<(usize, usize, usize)>::with_pattern(
+            input,
+            "move {} from {} to {}",
+        )
+        .collect::<Vec<_>>();
+
+        assert_eq!(vec![(3, 5, 2), (10, 1, 9)], moves);
+    }
+
+    #[test]
+    fn it_parses_negative_numbers_between_literals() {
+        let pattern = Pattern::new("Sensor at x={}, y={}: closest beacon is at x={}, y={}");
+
+        assert_eq!(
+            Ok((2, 18, -2, 15)),
+            pattern.parse::<(i32, i32, i32, i32)>(
+                "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
+            )
+        );
+    }
+
+    #[test]
+    fn it_is_flexible_with_whitespace() {
+        let pattern = Pattern::new("{}  ->   {}");
+
+        assert_eq!(
+            Ok(("a".to_string(), 'b')),
+            pattern.parse::<(String, char)>("a \t-> b  ")
+        );
+        assert!(pattern.parse::<(String, char)>("a->b").is_err());
+        assert_eq!(
+            Ok((12, 34)),
+            Pattern::new("{} {}").parse::<(u8, u8)>("12    34")
+        );
+    }
+
+    #[test]
+    fn it_parses_optional_and_repeated_fields() {
+        let pattern = Pattern::new("Game {}: {*,} [{?}] {*}");
+
+        assert_eq!(
+            Ok((1, vec![3, 4, 5], None, vec![1, 2])),
+            pattern.parse::<(u32, Vec<u32>, Option<String>, Vec<u8>)>("Game 1: 3, 4,5 [] 1 2")
+        );
+        assert_eq!(
+            Ok((2, vec![], Some("x".to_string()), vec![])),
+            pattern.parse::<(u32, Vec<u32>, Option<String>, Vec<u8>)>("Game 2:  [x]")
+        );
+    }
+
+    #[test]
+    fn it_parses_custom_types() {
+        let pattern = Pattern::new("{} {}");
+
+        let (Parsed(direction), steps) = pattern
+            .parse::<(Parsed<crate::point::Direction4>, u32)>("R 4")
+            .unwrap();
+
+        assert_eq!(crate::point::Direction4::Right, direction);
+        assert_eq!(4, steps);
+    }
+
+    #[test]
+    fn it_supports_escaped_braces() {
+        assert_eq!(Ok((1,)), Pattern::new("{{{}}}").parse::<(u8,)>("{1}"));
+    }
+
+    #[test]
+    fn it_reports_the_failing_placeholder() {
+        let mut iterator =
+            ParsingLineIterator::<(u32, u32)>::with_pattern("1-2\n3-x\n5+6", "{}-{}");
+
+        assert_eq!(Some(Ok((1, 2))), iterator.try_next());
+        assert_eq!(
+            Some(Err(PatternError {
+                line_number: 2,
+                line: "3-x".to_string(),
+                kind: PatternErrorKind::InvalidField {
+                    placeholder: 1,
+                    text: "x".to_string(),
+                    message: "ParseIntError { kind: InvalidDigit }".to_string(),
+                },
+            })),
+            iterator.try_next()
+        );
+        assert_eq!(
+            Some(Err(PatternError {
+                line_number: 3,
+                line: "5+6".to_string(),
+                kind: PatternErrorKind::LiteralMismatch {
+                    expected: "-".to_string(),
+                    position: 0,
+                },
+            })),
+            iterator.try_next()
+        );
+        assert_eq!(None, iterator.try_next());
+    }
+
+    #[test]
+    fn parse_checks_the_amount_of_placeholders() {
+        assert_eq!(
+            Err(PatternErrorKind::FieldCount {
+                expected: 3,
+                found: 2
+            }),
+            Pattern::new("{} {}").parse::<(u8, u8, u8)>("1 2")
+        );
+        assert_eq!(
+            Err(PatternErrorKind::FieldCount {
+                expected: 2,
+                found: 1
+            }),
+            <(u8, u8)>::from_captures(&[Capture {
+                text: "1",
+                kind: &FieldKind::Required
+            }])
+        );
+    }
+
+    #[test]
+    fn line_numbers_count_skipped_lines() {
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            comment_prefix: Some("#".to_string()),
+            ..Default::default()
+        };
+
+        let mut iterator = ParsingLineIterator::<(u32, u32)>::with_pattern_settings(
+            "# hdr\n\n1-2\n\n3-x",
+            "{}-{}",
+            settings,
+        );
+
+        assert_eq!(Some(Ok((1, 2))), iterator.try_next());
+        assert_eq!(5, iterator.try_next().unwrap().unwrap_err().line_number);
+    }
+
+    #[test]
+    fn it_reports_missing_and_trailing_text() {
+        let pattern = Pattern::new("a {} b");
+
+        assert_eq!(
+            Err(PatternErrorKind::MissingField { placeholder: 0 }),
+            pattern.parse::<(u8,)>("a  b")
+        );
+        assert_eq!(
+            Err(PatternErrorKind::TrailingText {
+                text: " c".to_string()
+            }),
+            pattern.parse::<(u8,)>("a 1 b c")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 \"3-x\": placeholder 1 (\"x\") is invalid")]
+    fn iterating_panics_with_the_line_number() {
+        ParsingLineIterator::<(u32, u32)>::with_pattern("1-2\n3-x", "{}-{}").for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "the pattern has 2 placeholders, but 3 values are expected")]
+    fn it_checks_the_amount_of_placeholders() {
+        ParsingLineIterator::<(u32, u32, u32)>::with_pattern("", "{}-{}");
+    }
+
+    #[test]
+    #[should_panic(expected = "placeholders must be separated by a literal")]
+    fn it_rejects_adjacent_placeholders() {
+        Pattern::new("{}{}");
+    }
+
+    #[test]
+    fn parsing_reports_syntax_errors() {
+        assert_eq!(
+            Err(PatternSyntaxError {
+                pattern: "{".to_string(),
+                kind: PatternSyntaxErrorKind::UnclosedPlaceholder,
+            }),
+            "{".parse::<Pattern>()
+        );
+        assert_eq!(
+            "unknown placeholder {x} in pattern \"a {x}\"",
+            Pattern::try_new("a {x}").unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(PatternSyntaxErrorKind::UnmatchedBrace),
+            "}".parse::<Pattern>().map_err(|error| error.kind)
+        );
+        assert_eq!(Ok(2), "{}-{}".parse::<Pattern>().map(|p| p.field_count()));
+    }
+}
@@ -0,0 +1,435 @@
+use crate::vec2d::{NEIGHBOURS4, NEIGHBOURS8};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A position or offset on a grid, using the same `(row, column)` convention as
+/// [`crate::vec2d::Vec2D`], so rows grow downwards.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point {
+    pub row: isize,
+    pub column: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(row: isize, column: isize) -> Self {
+        Self { row, column }
+    }
+
+    pub fn manhattan_distance(&self, other: Point) -> usize {
+        self.row.abs_diff(other.row) + self.column.abs_diff(other.column)
+    }
+
+    pub fn chebyshev_distance(&self, other: Point) -> usize {
+        self.row
+            .abs_diff(other.row)
+            .max(self.column.abs_diff(other.column))
+    }
+
+    /// The 4 orthogonal neighbours in the order of [`NEIGHBOURS4`], clockwise starting at the top.
+    pub fn neighbours4(&self) -> [Point; 4] {
+        NEIGHBOURS4.map(|offset| *self + Point::from(offset))
+    }
+
+    /// All 8 surrounding neighbours in the order of [`NEIGHBOURS8`], row by row.
+    pub fn neighbours8(&self) -> [Point; 8] {
+        NEIGHBOURS8.map(|offset| *self + Point::from(offset))
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((row, column): (isize, isize)) -> Self {
+        Self { row, column }
+    }
+}
+
+impl From<Point> for (isize, isize) {
+    fn from(point: Point) -> Self {
+        (point.row, point.column)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.row + rhs.row, self.column + rhs.column)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.row - rhs.row, self.column - rhs.column)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point::new(self.row * rhs, self.column * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.row, -self.column)
+    }
+}
+
+impl Add<Direction4> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction4) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl AddAssign<Direction4> for Point {
+    fn add_assign(&mut self, rhs: Direction4) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add<Direction8> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        self + rhs.delta()
+    }
+}
+
+impl AddAssign<Direction8> for Point {
+    fn add_assign(&mut self, rhs: Direction8) {
+        *self = *self + rhs;
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseDirectionError {
+    pub input: String,
+}
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid direction: {:?}", self.input)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Right,
+        Direction4::Down,
+        Direction4::Left,
+    ];
+
+    /// Turn 90 degrees counter-clockwise.
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Direction4::Up => Direction4::Left,
+            Direction4::Right => Direction4::Up,
+            Direction4::Down => Direction4::Right,
+            Direction4::Left => Direction4::Down,
+        }
+    }
+
+    /// Turn 90 degrees clockwise.
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Direction4::Up => Direction4::Right,
+            Direction4::Right => Direction4::Down,
+            Direction4::Down => Direction4::Left,
+            Direction4::Left => Direction4::Up,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction4::Up => Direction4::Down,
+            Direction4::Right => Direction4::Left,
+            Direction4::Down => Direction4::Up,
+            Direction4::Left => Direction4::Right,
+        }
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn delta(&self) -> Point {
+        match self {
+            Direction4::Up => Point::new(-1, 0),
+            Direction4::Right => Point::new(0, 1),
+            Direction4::Down => Point::new(1, 0),
+            Direction4::Left => Point::new(0, -1),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction4 {
+    type Error = ParseDirectionError;
+
+    /// Accepts `U/R/D/L`, `N/E/S/W` (in either case) and `^>v<`.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | 'u' | 'N' | 'n' | '^' => Ok(Direction4::Up),
+            'R' | 'r' | 'E' | 'e' | '>' => Ok(Direction4::Right),
+            'D' | 'd' | 'S' | 's' | 'v' | 'V' => Ok(Direction4::Down),
+            'L' | 'l' | 'W' | 'w' | '<' => Ok(Direction4::Left),
+            _ => Err(ParseDirectionError {
+                input: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(ParseDirectionError {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::Up => Direction8::Up,
+            Direction4::Right => Direction8::Right,
+            Direction4::Down => Direction8::Down,
+            Direction4::Left => Direction8::Left,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Turn 45 degrees counter-clockwise.
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turn 45 degrees clockwise.
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn delta(&self) -> Point {
+        match self {
+            Direction8::Up => Point::new(-1, 0),
+            Direction8::UpRight => Point::new(-1, 1),
+            Direction8::Right => Point::new(0, 1),
+            Direction8::DownRight => Point::new(1, 1),
+            Direction8::Down => Point::new(1, 0),
+            Direction8::DownLeft => Point::new(1, -1),
+            Direction8::Left => Point::new(0, -1),
+            Direction8::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    /// Accepts everything [`Direction4`] does, and the compass directions `NE`, `SE`, `SW` and `NW`
+    /// (in either case).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "NE" => Ok(Direction8::UpRight),
+            "SE" => Ok(Direction8::DownRight),
+            "SW" => Ok(Direction8::DownLeft),
+            "NW" => Ok(Direction8::UpLeft),
+            _ => s.parse::<Direction4>().map(Direction8::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic_works() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+
+        assert_eq!(Point::new(-2, 7), a + b);
+        assert_eq!(Point::new(4, -3), a - b);
+        assert_eq!(Point::new(-9, 15), b * 3);
+        assert_eq!(Point::new(-1, -2), -a);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+
+        assert_eq!(Point::new(0, 2), a + Direction4::Up);
+        assert_eq!(Point::new(2, 1), a + Direction8::DownLeft);
+    }
+
+    #[test]
+    fn distances_work() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+
+        assert_eq!(7, a.manhattan_distance(b));
+        assert_eq!(4, a.chebyshev_distance(b));
+    }
+
+    #[test]
+    fn neighbours_work() {
+        assert_eq!(
+            [
+                Point::new(-1, 0),
+                Point::new(0, 1),
+                Point::new(1, 0),
+                Point::new(0, -1)
+            ],
+            Point::ORIGIN.neighbours4()
+        );
+        assert_eq!(
+            [
+                Point::new(0, 1),
+                Point::new(0, 2),
+                Point::new(0, 3),
+                Point::new(1, 1),
+                Point::new(1, 3),
+                Point::new(2, 1),
+                Point::new(2, 2),
+                Point::new(2, 3)
+            ],
+            Point::new(1, 2).neighbours8()
+        );
+    }
+
+    #[test]
+    fn neighbours_match_vec2d() {
+        let grid = crate::vec2d::Vec2D::from(vec![0; 9], 3, 3);
+
+        assert_eq!(
+            grid.neighbours8(1, 1)
+                .map(|(row, column, _)| Point::new(row, column))
+                .collect::<Vec<_>>(),
+            Point::new(1, 1).neighbours8()
+        );
+        assert_eq!(
+            grid.neighbours4(1, 1)
+                .map(|(row, column, _)| Point::new(row, column))
+                .collect::<Vec<_>>(),
+            Point::new(1, 1).neighbours4()
+        );
+    }
+
+    #[test]
+    fn turning_works() {
+        assert_eq!(Direction4::Left, Direction4::Up.turn_left());
+        assert_eq!(Direction4::Right, Direction4::Up.turn_right());
+        assert_eq!(Direction4::Up, Direction4::Down.opposite());
+
+        assert_eq!(Direction8::UpLeft, Direction8::Up.turn_left());
+        assert_eq!(Direction8::Up, Direction8::UpLeft.turn_right());
+        assert_eq!(Direction8::DownLeft, Direction8::UpRight.opposite());
+
+        for direction in Direction8::ALL {
+            assert_eq!(-direction.delta(), direction.opposite().delta());
+        }
+    }
+
+    #[test]
+    fn parsing_directions_works() {
+        for (input, expected) in [
+            ("U", Direction4::Up),
+            ("N", Direction4::Up),
+            ("^", Direction4::Up),
+            ("R", Direction4::Right),
+            ("e", Direction4::Right),
+            (">", Direction4::Right),
+            ("D", Direction4::Down),
+            ("S", Direction4::Down),
+            ("v", Direction4::Down),
+            ("L", Direction4::Left),
+            ("W", Direction4::Left),
+            ("<", Direction4::Left),
+        ] {
+            assert_eq!(Ok(expected), input.parse(), "parsing {input}");
+        }
+
+        assert_eq!(
+            Err(ParseDirectionError {
+                input: "UP".to_string()
+            }),
+            "UP".parse::<Direction4>()
+        );
+
+        assert_eq!(Ok(Direction8::UpRight), "NE".parse());
+        assert_eq!(Ok(Direction8::Left), "<".parse());
+        assert!("X".parse::<Direction8>().is_err());
+    }
+}
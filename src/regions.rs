@@ -0,0 +1,258 @@
+use crate::vec2d::{Vec2D, NEIGHBOURS4, NEIGHBOURS8};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Which cells count as connected to a cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Connectivity {
+    /// Only the orthogonal neighbours
+    Four,
+    /// The orthogonal and diagonal neighbours
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &NEIGHBOURS4,
+            Connectivity::Eight => &NEIGHBOURS8,
+        }
+    }
+}
+
+/// Statistics of a connected region, see [`Vec2D::label_components`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Region {
+    pub label: usize,
+    /// The amount of cells in the region
+    pub area: usize,
+    /// The amount of cell edges bordering another region or the outside of the grid
+    pub perimeter: usize,
+    /// The amount of straight fence sections around the region, which is the same as the amount of
+    /// corners
+    pub sides: usize,
+    /// The rows spanned by the region
+    pub rows: Range<isize>,
+    /// The columns spanned by the region
+    pub columns: Range<isize>,
+}
+
+impl<T> Vec2D<T> {
+    /// Find all cells connected to `start` through cells matching the predicate. The start itself is
+    /// only included if it matches.
+    pub fn flood_fill<P>(
+        &self,
+        start: (isize, isize),
+        connectivity: Connectivity,
+        mut predicate: P,
+    ) -> HashSet<(isize, isize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut visited = HashSet::new();
+
+        if !self.at(start.0, start.1).is_some_and(&mut predicate) {
+            return visited;
+        }
+
+        let mut stack = vec![start];
+        visited.insert(start);
+
+        while let Some((row, column)) = stack.pop() {
+            for (row, column, cell) in self.neighbours_with(row, column, connectivity.offsets()) {
+                if !visited.contains(&(row, column)) && predicate(cell) {
+                    visited.insert((row, column));
+                    stack.push((row, column));
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Group all cells into connected regions, where neighbouring cells belong to the same region if
+    /// `same_region` says so. Returns a map with the label of every cell, and the statistics of
+    /// every region indexed by label.
+    ///
+    /// The perimeter and sides are always measured along the orthogonal edges of the cells.
+    ///
+    /// Panics if the grid is still being filled with [`Vec2D::push`] and not every cell holds a
+    /// value yet.
+    #[track_caller]
+    pub fn label_components<F>(
+        &self,
+        connectivity: Connectivity,
+        mut same_region: F,
+    ) -> (Vec2D<usize>, Vec<Region>)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.assert_filled();
+
+        let mut labels = Vec2D::new_sized_like(self, usize::MAX);
+        let mut regions = Vec::new();
+
+        for ((row, column), _) in self.indexed_iter() {
+            if *labels.at_unchecked(row, column) != usize::MAX {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region {
+                label,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                rows: row..row + 1,
+                columns: column..column + 1,
+            };
+
+            *labels.at_mut_unchecked(row, column) = label;
+            let mut stack = vec![(row, column)];
+
+            while let Some((row, column)) = stack.pop() {
+                region.area += 1;
+                region.rows = region.rows.start.min(row)..region.rows.end.max(row + 1);
+                region.columns =
+                    region.columns.start.min(column)..region.columns.end.max(column + 1);
+
+                let cell = self.at_unchecked(row, column);
+
+                for (next_row, next_column, next) in
+                    self.neighbours_with(row, column, connectivity.offsets())
+                {
+                    let next_label = labels.at_mut_unchecked(next_row, next_column);
+
+                    if *next_label == usize::MAX && same_region(cell, next) {
+                        *next_label = label;
+                        stack.push((next_row, next_column));
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        for ((row, column), &label) in labels.indexed_iter() {
+            let same = |row_offset: isize, column_offset: isize| {
+                labels.at(row + row_offset, column + column_offset) == Some(&label)
+            };
+
+            regions[label].perimeter += NEIGHBOURS4
+                .iter()
+                .filter(|(row_offset, column_offset)| !same(*row_offset, *column_offset))
+                .count();
+
+            // Every corner of the outline starts a new side
+            for (row_offset, column_offset) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+                let vertical = same(row_offset, 0);
+                let horizontal = same(0, column_offset);
+                let diagonal = same(row_offset, column_offset);
+
+                if (!vertical && !horizontal) || (vertical && horizontal && !diagonal) {
+                    regions[label].sides += 1;
+                }
+            }
+        }
+
+        (labels, regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flood_fill_works() {
+        let grid: Vec2D<char> = "..#\n.##\n#..".parse().unwrap();
+
+        let filled = grid.flood_fill((0, 0), Connectivity::Four, |c| *c == '.');
+        assert_eq!(HashSet::from([(0, 0), (0, 1), (1, 0)]), filled);
+
+        let filled = grid.flood_fill((0, 0), Connectivity::Eight, |c| *c == '.');
+        assert_eq!(5, filled.len());
+
+        assert!(grid
+            .flood_fill((0, 2), Connectivity::Four, |c| *c == '.')
+            .is_empty());
+    }
+
+    #[test]
+    fn flood_fill_respects_negative_coordinates() {
+        let grid = Vec2D::from_negative(vec![1, 1, 0, 1], 1, 1, 1, 1);
+
+        assert_eq!(
+            HashSet::from([(-1, -1), (-1, 0), (0, 0)]),
+            grid.flood_fill((0, 0), Connectivity::Four, |value| *value == 1)
+        );
+    }
+
+    #[test]
+    fn label_components_works() {
+        let grid: Vec2D<char> = "\
+AAAA
+BBCD
+BBCC
+EEEC"
+            .parse()
+            .unwrap();
+
+        let (labels, regions) = grid.label_components(Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(5, regions.len());
+        assert_eq!(
+            vec![0, 0, 0, 0, 1, 1, 2, 3, 1, 1, 2, 2, 4, 4, 4, 2],
+            labels.into_iter().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)],
+            regions
+                .iter()
+                .map(|region| (region.area, region.perimeter, region.sides))
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(1..4, regions[2].rows);
+        assert_eq!(2..4, regions[2].columns);
+    }
+
+    #[test]
+    fn label_components_counts_inner_sides() {
+        let grid: Vec2D<char> = "\
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA"
+            .parse()
+            .unwrap();
+
+        let (_, regions) = grid.label_components(Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(3, regions.len());
+        assert_eq!(28, regions[0].area);
+        assert_eq!(12, regions[0].sides);
+    }
+
+    #[test]
+    fn label_components_with_eight_connectivity() {
+        let grid: Vec2D<char> = "#.\n.#".parse().unwrap();
+
+        let (_, regions) = grid.label_components(Connectivity::Eight, |a, b| a == b);
+
+        assert_eq!(2, regions.len());
+        assert_eq!(2, regions[0].area);
+    }
+
+    #[test]
+    #[should_panic(expected = "only 3 of the 2x2 cells of the grid are filled")]
+    fn label_components_panics_on_a_partially_filled_grid() {
+        let mut grid = Vec2D::with_capacity_and_size(4, 2, 2);
+        "AAB".chars().for_each(|c| grid.push(c));
+
+        grid.label_components(Connectivity::Four, |a, b| a == b);
+    }
+}
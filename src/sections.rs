@@ -0,0 +1,345 @@
+use crate::line_iterator::{next_line_end, LineIterator, LineIteratorSettings};
+use crate::parsing_line_iterator::ParsingLineIterator;
+use crate::pattern::Parsed;
+use crate::vec2d::Vec2D;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Conversion from a single section of the input, see [`FromSections`].
+///
+/// Panics if the section cannot be parsed.
+pub trait FromSection<'a>: Sized {
+    fn from_section(section: LineIterator<'a>) -> Self;
+}
+
+impl<'a> FromSection<'a> for LineIterator<'a> {
+    fn from_section(section: LineIterator<'a>) -> Self {
+        section
+    }
+}
+
+impl<'a, T> FromSection<'a> for ParsingLineIterator<'a, T> {
+    fn from_section(section: LineIterator<'a>) -> Self {
+        section.into()
+    }
+}
+
+impl<'a> FromSection<'a> for &'a str {
+    fn from_section(section: LineIterator<'a>) -> Self {
+        section.as_str()
+    }
+}
+
+/// The lines of the section joined without their line endings, like a move string wrapped over
+/// multiple lines.
+impl<'a> FromSection<'a> for String {
+    fn from_section(section: LineIterator<'a>) -> Self {
+        section.collect()
+    }
+}
+
+/// Every line of the section parsed into a `T`.
+impl<'a, T> FromSection<'a> for Vec<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    fn from_section(section: LineIterator<'a>) -> Self {
+        ParsingLineIterator::<T>::from_section(section).collect()
+    }
+}
+
+impl<'a, T> FromSection<'a> for Vec2D<T>
+where
+    Vec2D<T>: FromStr,
+    <Vec2D<T> as FromStr>::Err: Debug,
+{
+    fn from_section(section: LineIterator<'a>) -> Self {
+        parse_section(&section, section.as_str())
+    }
+}
+
+/// The whole section parsed at once.
+impl<'a, T> FromSection<'a> for Parsed<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    fn from_section(section: LineIterator<'a>) -> Self {
+        Parsed(parse_section(&section, section.as_str().trim()))
+    }
+}
+
+fn parse_section<T>(section: &LineIterator, text: &str) -> T
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    text.parse().unwrap_or_else(|error| {
+        panic!(
+            "failed to parse the section starting at line {}: {error:?}",
+            section.position().1 + 1
+        )
+    })
+}
+
+/// Conversion from all sections of the input, implemented for tuples of [`FromSection`] types.
+pub trait FromSections<'a>: Sized {
+    fn from_sections<I>(sections: I) -> Result<Self, String>
+    where
+        I: Iterator<Item = LineIterator<'a>>;
+}
+
+macro_rules! impl_from_sections {
+    ($count:expr; $($t:ident),*) => {
+        impl<'a, $($t),*> FromSections<'a> for ($($t,)*)
+        where
+            $($t: FromSection<'a>),*
+        {
+            fn from_sections<I>(sections: I) -> Result<Self, String>
+            where
+                I: Iterator<Item = LineIterator<'a>>,
+            {
+                let sections = sections.collect::<Vec<_>>();
+
+                if sections.len() != $count {
+                    return Err(format!(
+                        "expected {} sections, found {}",
+                        $count,
+                        sections.len()
+                    ));
+                }
+
+                let mut sections = sections.into_iter();
+
+                Ok(($($t::from_section(sections.next().unwrap()),)*))
+            }
+        }
+    };
+}
+
+impl_from_sections!(1; A);
+impl_from_sections!(2; A, B);
+impl_from_sections!(3; A, B, C);
+impl_from_sections!(4; A, B, C, D);
+impl_from_sections!(5; A, B, C, D, E);
+impl_from_sections!(6; A, B, C, D, E, F);
+impl_from_sections!(7; A, B, C, D, E, F, G);
+impl_from_sections!(8; A, B, C, D, E, F, G, H);
+
+/// Iterates over the sections that start with a header line, yielding the lines below each
+/// header as their own [`LineIterator`]. Blank lines around a section are left out, as are any
+/// lines before the first header if they are all blank.
+pub struct HeaderSectionIterator<'a, F> {
+    input: &'a str,
+    settings: LineIteratorSettings,
+    offset: usize,
+    index: usize,
+    is_header: F,
+}
+
+impl<'a, F> HeaderSectionIterator<'a, F>
+where
+    F: FnMut(&str) -> bool,
+{
+    pub fn from(input: &'a str, is_header: F) -> Self {
+        Self::from_line_iterator(LineIterator::from(input), is_header)
+    }
+
+    pub fn from_settings(input: &'a str, settings: LineIteratorSettings, is_header: F) -> Self {
+        Self::from_line_iterator(LineIterator::from_settings(input, settings), is_header)
+    }
+
+    pub fn from_line_iterator(line_iterator: LineIterator<'a>, is_header: F) -> Self {
+        let (offset, index) = line_iterator.position();
+
+        Self {
+            input: line_iterator.as_str(),
+            settings: line_iterator.settings().clone(),
+            offset,
+            index,
+            is_header,
+        }
+    }
+
+    /// Consume the first line of the remaining input.
+    fn advance(&mut self) -> &'a str {
+        let line_end = next_line_end(self.input);
+        let (line, rest) = self.input.split_at(line_end);
+
+        self.input = rest;
+        self.offset += line_end;
+        self.index += 1;
+
+        line
+    }
+}
+
+impl<'a, F> Iterator for HeaderSectionIterator<'a, F>
+where
+    F: FnMut(&str) -> bool,
+{
+    type Item = LineIterator<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.input.is_empty() {
+                return None;
+            }
+
+            let starts_with_header = {
+                let first_line = self.input.lines().next().unwrap_or_default();
+
+                (self.is_header)(first_line.trim())
+            };
+
+            if starts_with_header {
+                self.advance();
+            }
+
+            // Leave out blank lines in front of the section
+            while !self.input.is_empty() && self.input.lines().next().unwrap().trim().is_empty() {
+                self.advance();
+            }
+
+            let (offset, index) = (self.offset, self.index);
+            let start = self.input;
+            let mut length = 0;
+
+            // Take lines until the next header, remembering where the last non-blank one ended
+            while let Some(line) = self.input.lines().next() {
+                if (self.is_header)(line.trim()) {
+                    break;
+                }
+
+                let line = self.advance();
+
+                if !line.trim().is_empty() {
+                    length = self.offset - offset;
+                }
+            }
+
+            if starts_with_header || length > 0 {
+                return Some(LineIterator::starting_at(
+                    &start[..length],
+                    self.settings.clone(),
+                    offset,
+                    index,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Move {
+        count: usize,
+        from: usize,
+        to: usize,
+    }
+
+    impl FromStr for Move {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let numbers = s
+                .split(' ')
+                .filter_map(|word| word.parse().ok())
+                .collect::<Vec<_>>();
+
+            match numbers[..] {
+                [count, from, to] => Ok(Self { count, from, to }),
+                _ => Err(format!("invalid move {s:?}")),
+            }
+        }
+    }
+
+    #[test]
+    fn it_parses_blank_line_separated_sections() {
+        let input = "#.#\n..#\n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\n\n<>><\n>>\n";
+
+        let (grid, moves, directions): (Vec2D<char>, ParsingLineIterator<Move>, String) =
+            LineIterator::from(input).sections();
+
+        assert_eq!("#.#\n..#".parse::<Vec2D<char>>().unwrap(), grid);
+        assert_eq!(
+            vec![
+                Move {
+                    count: 1,
+                    from: 2,
+                    to: 1
+                },
+                Move {
+                    count: 3,
+                    from: 1,
+                    to: 3
+                }
+            ],
+            moves.collect::<Vec<_>>()
+        );
+        assert_eq!("<>><>>", directions);
+    }
+
+    #[test]
+    fn it_splits_on_header_lines() {
+        let input = "ignored?\n\nRules:\n\n0: 1 2\n1: \"a\"\n\nMessages:\nab\nba\n\nEmpty:\n";
+
+        let sections = LineIterator::from(input)
+            .header_groups(|line| line.ends_with(':'))
+            .map(|section| section.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                vec!["ignored?"],
+                vec!["0: 1 2", "1: \"a\""],
+                vec!["ab", "ba"],
+                vec![]
+            ],
+            sections
+        );
+    }
+
+    #[test]
+    fn header_sections_parse_into_tuples() {
+        let input = "[numbers]\n1\n2\n\n[word]\nabc\n";
+
+        let (numbers, Parsed(word)): (Vec<u32>, Parsed<String>) =
+            LineIterator::from(input).sections_by_header(|line| line.starts_with('['));
+
+        assert_eq!(vec![1, 2], numbers);
+        assert_eq!("abc", word);
+    }
+
+    #[test]
+    fn sections_keep_their_positions() {
+        let input = "a\n\nheader\n\nb\nc\n";
+
+        let positions = LineIterator::from(input)
+            .header_groups(|line| line == "header")
+            .flat_map(LineIterator::with_positions)
+            .map(|line| (line.text, line.index, line.span))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![("a", 0, 0..2), ("b", 4, 11..13), ("c", 5, 13..15)],
+            positions
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the section starting at line 4: ParseIntError")]
+    fn it_panics_with_the_line_number_of_the_section() {
+        let _: (Vec2D<char>, Parsed<u32>) = LineIterator::from("#.\n.#\n\nx\n").sections();
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 sections, found 3")]
+    fn it_panics_on_the_wrong_number_of_sections() {
+        let _: (&str, &str) = LineIterator::from("a\n\nb\n\nc").sections();
+    }
+}
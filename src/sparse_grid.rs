@@ -0,0 +1,423 @@
+use crate::vec2d::Vec2D;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// An unbounded grid that only stores the cells that have been set, every other cell holds the
+/// default value. Positions are `(row, column)` like in [`Vec2D`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+    default: T,
+    /// Inclusive, so the bounding box can reach `isize::MAX`, empty if no cells are set
+    rows: RangeInclusive<isize>,
+    columns: RangeInclusive<isize>,
+}
+
+/// The bounds of an empty grid.
+const EMPTY: RangeInclusive<isize> = RangeInclusive::new(1, 0);
+
+impl<T> SparseGrid<T> {
+    /// Create an empty grid where every cell holds `default` until it is set.
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            rows: EMPTY,
+            columns: EMPTY,
+        }
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// The amount of cells that have been set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The rows spanned by the set cells, empty if there are none.
+    pub fn row_range(&self) -> RangeInclusive<isize> {
+        self.rows.clone()
+    }
+
+    /// The columns spanned by the set cells, empty if there are none.
+    pub fn column_range(&self) -> RangeInclusive<isize> {
+        self.columns.clone()
+    }
+
+    pub fn positive_width(&self) -> usize {
+        positive_extent(&self.columns)
+    }
+
+    pub fn negative_width(&self) -> usize {
+        negative_extent(&self.columns)
+    }
+
+    /// Width of the bounding box, including column 0 like a [`Vec2D`] would, saturating at
+    /// `usize::MAX`.
+    pub fn width(&self) -> usize {
+        self.positive_width().saturating_add(self.negative_width())
+    }
+
+    pub fn positive_height(&self) -> usize {
+        positive_extent(&self.rows)
+    }
+
+    pub fn negative_height(&self) -> usize {
+        negative_extent(&self.rows)
+    }
+
+    /// Height of the bounding box, including row 0 like a [`Vec2D`] would, saturating at
+    /// `usize::MAX`.
+    pub fn height(&self) -> usize {
+        self.positive_height()
+            .saturating_add(self.negative_height())
+    }
+
+    /// Whether the position lies within the bounding box of the set cells.
+    pub fn contains(&self, row: isize, column: isize) -> bool {
+        self.rows.contains(&row) && self.columns.contains(&column)
+    }
+
+    /// Whether the cell at the position has been set.
+    pub fn is_set(&self, row: isize, column: isize) -> bool {
+        self.cells.contains_key(&(row, column))
+    }
+
+    /// The value of the cell, which is the default value if it has not been set.
+    pub fn get(&self, row: isize, column: isize) -> &T {
+        self.cells.get(&(row, column)).unwrap_or(&self.default)
+    }
+
+    /// Access the cell mutably, setting it to the default value first if it has not been set.
+    pub fn get_mut(&mut self, row: isize, column: isize) -> &mut T
+    where
+        T: Clone,
+    {
+        self.include(row, column);
+
+        self.cells
+            .entry((row, column))
+            .or_insert_with(|| self.default.clone())
+    }
+
+    /// Like [`Vec2D::at`], the value of the cell if the position lies within the bounding box.
+    pub fn at(&self, row: isize, column: isize) -> Option<&T> {
+        self.contains(row, column).then(|| self.get(row, column))
+    }
+
+    /// Like [`Vec2D::at_mut`], access the cell mutably if the position lies within the bounding
+    /// box, setting it to the default value first if it has not been set.
+    pub fn at_mut(&mut self, row: isize, column: isize) -> Option<&mut T>
+    where
+        T: Clone,
+    {
+        if !self.contains(row, column) {
+            return None;
+        }
+
+        Some(self.get_mut(row, column))
+    }
+
+    /// Convert a [`Vec2D`], only setting the cells that differ from `default`.
+    pub fn from_vec2d(vec2d: Vec2D<T>, default: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        let mut sparse_grid = SparseGrid::new(default);
+
+        for ((row, column), value) in vec2d.indexed_iter() {
+            if *value != sparse_grid.default {
+                sparse_grid.set(row, column, value.clone());
+            }
+        }
+
+        sparse_grid
+    }
+
+    /// Set the cell, returning the previous value if it was set.
+    pub fn set(&mut self, row: isize, column: isize, value: T) -> Option<T> {
+        self.include(row, column);
+
+        self.cells.insert((row, column), value)
+    }
+
+    /// Unset the cell, so it holds the default value again, shrinking the bounding box if needed.
+    pub fn remove(&mut self, row: isize, column: isize) -> Option<T> {
+        let value = self.cells.remove(&(row, column))?;
+
+        if row == *self.rows.start()
+            || row == *self.rows.end()
+            || column == *self.columns.start()
+            || column == *self.columns.end()
+        {
+            self.rows = bounds(self.cells.keys().map(|&(row, _)| row));
+            self.columns = bounds(self.cells.keys().map(|&(_, column)| column));
+        }
+
+        Some(value)
+    }
+
+    /// Iterate over the cells that have been set in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> + '_ {
+        self.cells
+            .iter()
+            .map(|(position, value)| (*position, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((isize, isize), &mut T)> + '_ {
+        self.cells
+            .iter_mut()
+            .map(|(position, value)| (*position, value))
+    }
+
+    /// Find the `(row, column)` positions of all set cells matching the predicate.
+    pub fn positions_where<'a, P>(
+        &'a self,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (isize, isize)> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.iter()
+            .filter_map(move |(position, value)| predicate(value).then_some(position))
+    }
+
+    /// Grow the bounding box to include the position.
+    fn include(&mut self, row: isize, column: isize) {
+        if self.cells.is_empty() {
+            self.rows = row..=row;
+            self.columns = column..=column;
+
+            return;
+        }
+
+        self.rows = row.min(*self.rows.start())..=row.max(*self.rows.end());
+        self.columns = column.min(*self.columns.start())..=column.max(*self.columns.end());
+    }
+}
+
+/// The smallest range containing all values, empty if there are none.
+fn bounds<I>(values: I) -> RangeInclusive<isize>
+where
+    I: Iterator<Item = isize> + Clone,
+{
+    match (values.clone().min(), values.max()) {
+        (Some(min), Some(max)) => min..=max,
+        _ => EMPTY,
+    }
+}
+
+/// The amount of positions from 0 up to the end of the range, 0 included.
+fn positive_extent(range: &RangeInclusive<isize>) -> usize {
+    if range.is_empty() || *range.end() < 0 {
+        0
+    } else {
+        range.end().unsigned_abs() + 1
+    }
+}
+
+/// The amount of negative positions from the start of the range up to 0.
+fn negative_extent(range: &RangeInclusive<isize>) -> usize {
+    if range.is_empty() || *range.start() >= 0 {
+        0
+    } else {
+        range.start().unsigned_abs()
+    }
+}
+
+impl<T> Default for SparseGrid<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<Vec2D<T>> for SparseGrid<T>
+where
+    T: Clone + Default + PartialEq,
+{
+    /// Only the cells that differ from `T::default()` are set, see [`SparseGrid::from_vec2d`] for
+    /// other defaults.
+    fn from(vec2d: Vec2D<T>) -> Self {
+        SparseGrid::from_vec2d(vec2d, T::default())
+    }
+}
+
+impl<T> From<SparseGrid<T>> for Vec2D<T>
+where
+    T: Clone,
+{
+    /// The grid covers the bounding box and the origin, unset cells hold the default value.
+    fn from(sparse_grid: SparseGrid<T>) -> Self {
+        let mut vec2d = Vec2D::new_sized_with(
+            sparse_grid.positive_width(),
+            sparse_grid.positive_height(),
+            sparse_grid.negative_width(),
+            sparse_grid.negative_height(),
+            sparse_grid.default,
+        );
+
+        for ((row, column), value) in sparse_grid.cells {
+            *vec2d.at_mut_unchecked(row, column) = value;
+        }
+
+        vec2d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_cells_hold_the_default() {
+        let mut sparse_grid = SparseGrid::new('.');
+
+        sparse_grid.set(-1_000_000, 5, '#');
+
+        assert_eq!(&'#', sparse_grid.get(-1_000_000, 5));
+        assert_eq!(&'.', sparse_grid.get(0, 0));
+        assert_eq!(&'.', sparse_grid.get(isize::MAX, isize::MIN));
+        assert_eq!(1, sparse_grid.len());
+    }
+
+    #[test]
+    fn at_mut_sets_the_cell() {
+        let mut sparse_grid: SparseGrid<u32> = SparseGrid::default();
+
+        *sparse_grid.get_mut(2, -3) += 5;
+        *sparse_grid.get_mut(2, -3) += 5;
+
+        assert_eq!(&10, sparse_grid.get(2, -3));
+        assert!(sparse_grid.is_set(2, -3));
+        assert!(!sparse_grid.is_set(0, 0));
+    }
+
+    #[test]
+    fn it_tracks_the_bounding_box() {
+        let mut sparse_grid: SparseGrid<bool> = SparseGrid::default();
+        assert!(sparse_grid.row_range().is_empty());
+
+        sparse_grid.set(3, 4, true);
+        assert_eq!(3..=3, sparse_grid.row_range());
+        assert_eq!(4..=4, sparse_grid.column_range());
+
+        sparse_grid.set(-2, 7, true);
+        sparse_grid.set(1, -5, true);
+        assert_eq!(-2..=3, sparse_grid.row_range());
+        assert_eq!(-5..=7, sparse_grid.column_range());
+        assert!(sparse_grid.contains(0, 0));
+        assert!(!sparse_grid.contains(4, 0));
+
+        assert_eq!(8, sparse_grid.positive_width());
+        assert_eq!(5, sparse_grid.negative_width());
+        assert_eq!(4, sparse_grid.positive_height());
+        assert_eq!(2, sparse_grid.negative_height());
+
+        assert_eq!(Some(true), sparse_grid.remove(-2, 7));
+        assert_eq!(1..=3, sparse_grid.row_range());
+        assert_eq!(-5..=4, sparse_grid.column_range());
+    }
+
+    #[test]
+    fn it_tracks_the_bounding_box_at_the_extremes() {
+        let mut sparse_grid = SparseGrid::new(0);
+
+        sparse_grid.set(isize::MAX, isize::MIN, 1);
+        *sparse_grid.get_mut(isize::MIN, isize::MAX) += 2;
+
+        assert_eq!(isize::MIN..=isize::MAX, sparse_grid.row_range());
+        assert_eq!(isize::MIN..=isize::MAX, sparse_grid.column_range());
+        assert_eq!(Some(&1), sparse_grid.at(isize::MAX, isize::MIN));
+        assert_eq!(isize::MAX as usize + 1, sparse_grid.positive_height());
+        assert_eq!(isize::MIN.unsigned_abs(), sparse_grid.negative_width());
+        assert_eq!(usize::MAX, sparse_grid.width());
+
+        sparse_grid.remove(isize::MIN, isize::MAX);
+        assert_eq!(isize::MAX..=isize::MAX, sparse_grid.row_range());
+        assert!(!sparse_grid.contains(0, 0));
+
+        sparse_grid.remove(isize::MAX, isize::MIN);
+        assert!(sparse_grid.row_range().is_empty());
+    }
+
+    #[test]
+    fn it_converts_from_vec2d() {
+        let vec2d = Vec2D::from_negative(
+            vec![
+                0, 1, //
+                2, 0, //
+            ],
+            1,
+            1,
+            1,
+            1,
+        );
+
+        let sparse_grid: SparseGrid<i32> = vec2d.into();
+
+        assert_eq!(2, sparse_grid.len());
+        assert_eq!(&1, sparse_grid.get(-1, 0));
+        assert_eq!(&2, sparse_grid.get(0, -1));
+        assert!(!sparse_grid.is_set(-1, -1));
+    }
+
+    #[test]
+    fn at_only_covers_the_bounding_box() {
+        let mut sparse_grid = SparseGrid::new('.');
+        sparse_grid.set(0, 0, '#');
+        sparse_grid.set(1, 2, '#');
+
+        assert_eq!(Some(&'.'), sparse_grid.at(0, 1));
+        assert_eq!(Some(&'#'), sparse_grid.at(1, 2));
+        assert_eq!(None, sparse_grid.at(2, 0));
+
+        *sparse_grid.at_mut(1, 0).unwrap() = 'x';
+        assert!(sparse_grid.at_mut(-1, 0).is_none());
+        assert_eq!(3, sparse_grid.len());
+    }
+
+    #[test]
+    fn it_converts_from_vec2d_with_a_default() {
+        let vec2d: Vec2D<char> = "..#\n#..".parse().unwrap();
+
+        let sparse_grid = SparseGrid::from_vec2d(vec2d, '.');
+
+        assert_eq!(&'.', sparse_grid.default_value());
+        assert_eq!(2, sparse_grid.len());
+        assert_eq!(&'#', sparse_grid.get(0, 2));
+        assert_eq!(&'.', sparse_grid.get(0, 0));
+    }
+
+    #[test]
+    fn it_converts_into_vec2d() {
+        let mut sparse_grid = SparseGrid::new('.');
+        sparse_grid.set(-1, -2, 'S');
+        sparse_grid.set(1, 0, 'E');
+
+        let vec2d: Vec2D<char> = sparse_grid.into();
+
+        assert_eq!(
+            Vec2D::from_negative(
+                vec![
+                    'S', '.', '.', //
+                    '.', '.', '.', //
+                    '.', '.', 'E', //
+                ],
+                1,
+                2,
+                2,
+                1,
+            ),
+            vec2d
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use crate::ints::wrong_amount;
+use crate::line_iterator::{LineIterator, LineIteratorSettings, TrimMode};
+use crate::pattern::{for_each_leaf_type, Capture, FieldKind, FromCapture, Parsed};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// The separators left for a value and everything nested in it, outermost first.
+#[derive(Clone, Copy, Debug)]
+pub struct Separators<'s> {
+    separators: &'s [String],
+    trim_mode: &'s TrimMode,
+}
+
+impl<'s> Separators<'s> {
+    pub fn new(separators: &'s [String], trim_mode: &'s TrimMode) -> Self {
+        Self {
+            separators,
+            trim_mode,
+        }
+    }
+
+    /// Split `text` on the outermost separator, returning the parts and the separators left for
+    /// them. A separator consisting only of whitespace splits on any run of whitespace.
+    pub fn split<'t>(&self, text: &'t str) -> Result<(Vec<&'t str>, Separators<'s>), String> {
+        let (separator, nested) = self
+            .separators
+            .split_first()
+            .ok_or_else(|| format!("no separator left to split {text:?}"))?;
+
+        let parts = if text.is_empty() {
+            Vec::new()
+        } else if separator.trim().is_empty() {
+            text.split_whitespace().collect()
+        } else {
+            text.split(separator.as_str())
+                .map(|part| self.trim_mode.trim(part))
+                .collect()
+        };
+
+        Ok((parts, Separators::new(nested, self.trim_mode)))
+    }
+}
+
+/// Conversion from text that may be split further using [`Separators`].
+pub trait FromSplit: Sized {
+    fn from_split(text: &str, separators: Separators) -> Result<Self, String>;
+}
+
+/// Parse a part that is not split any further like a required pattern placeholder.
+fn parse_part<T>(text: &str) -> Result<T, String>
+where
+    T: FromCapture,
+{
+    T::from_capture(Capture {
+        text,
+        kind: &FieldKind::Required,
+    })
+    .map_err(|error| format!("invalid value {text:?}: {error}"))
+}
+
+macro_rules! impl_from_split {
+    ($($t:ty),*) => {
+        $(
+            impl FromSplit for $t {
+                fn from_split(text: &str, _: Separators) -> Result<Self, String> {
+                    parse_part(text)
+                }
+            }
+        )*
+    };
+}
+
+for_each_leaf_type!(impl_from_split);
+
+impl<T> FromSplit for Parsed<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    fn from_split(text: &str, _: Separators) -> Result<Self, String> {
+        parse_part(text)
+    }
+}
+
+impl<T> FromSplit for Option<T>
+where
+    T: FromSplit,
+{
+    fn from_split(text: &str, separators: Separators) -> Result<Self, String> {
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        T::from_split(text, separators).map(Some)
+    }
+}
+
+impl<T> FromSplit for Vec<T>
+where
+    T: FromSplit,
+{
+    fn from_split(text: &str, separators: Separators) -> Result<Self, String> {
+        let (parts, nested) = separators.split(text)?;
+
+        parts
+            .into_iter()
+            .map(|part| T::from_split(part, nested))
+            .collect()
+    }
+}
+
+impl<T, const N: usize> FromSplit for [T; N]
+where
+    T: FromSplit,
+{
+    fn from_split(text: &str, separators: Separators) -> Result<Self, String> {
+        let values = Vec::<T>::from_split(text, separators)?;
+        let found = values.len();
+
+        values
+            .try_into()
+            .map_err(|_| wrong_amount(N, found, "parts", text))
+    }
+}
+
+macro_rules! impl_from_split_tuple {
+    ($count:expr; $($t:ident),*) => {
+        impl<$($t),*> FromSplit for ($($t,)*)
+        where
+            $($t: FromSplit),*
+        {
+            fn from_split(text: &str, separators: Separators) -> Result<Self, String> {
+                let (parts, nested) = separators.split(text)?;
+
+                if parts.len() != $count {
+                    return Err(wrong_amount($count, parts.len(), "parts", text));
+                }
+
+                let mut parts = parts.into_iter();
+
+                Ok(($($t::from_split(parts.next().unwrap(), nested)?,)*))
+            }
+        }
+    };
+}
+
+impl_from_split_tuple!(2; A, B);
+impl_from_split_tuple!(3; A, B, C);
+impl_from_split_tuple!(4; A, B, C, D);
+impl_from_split_tuple!(5; A, B, C, D, E);
+impl_from_split_tuple!(6; A, B, C, D, E, F);
+impl_from_split_tuple!(7; A, B, C, D, E, F, G);
+impl_from_split_tuple!(8; A, B, C, D, E, F, G, H);
+
+/// Parses every line by splitting it on a chain of separators. Each level of [`Vec`], array or
+/// tuple in `T` uses the next separator, so `Vec<(u32, u32)>` with `", "` and `"-"` parses
+/// `2-4, 6-8`. The parts themselves are parsed using [`FromStr`].
+///
+/// The primitive types and `String` can be used for the parts directly. Other [`FromStr`] types,
+/// including those using `#[derive(LineParse)]`, have to be wrapped in [`Parsed`]: without
+/// specialization a blanket implementation for every [`FromStr`] type would overlap with the one
+/// for [`Vec`] and the other containers.
+///
+/// Panics with the failing line if a line cannot be parsed.
+pub struct SplitParsingIterator<'a, T> {
+    line_iterator: LineIterator<'a>,
+    separators: Vec<String>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> SplitParsingIterator<'a, T> {
+    pub fn from(input: &'a str, separator: &str) -> Self {
+        Self::from_settings(input, separator, LineIteratorSettings::default())
+    }
+
+    pub fn from_settings(input: &'a str, separator: &str, settings: LineIteratorSettings) -> Self {
+        Self::from_line_iterator(
+            LineIterator::from_settings(input, settings),
+            vec![separator.to_string()],
+        )
+    }
+
+    pub fn from_line_iterator(line_iterator: LineIterator<'a>, separators: Vec<String>) -> Self {
+        Self {
+            line_iterator,
+            separators,
+            marker: PhantomData,
+        }
+    }
+
+    /// Add a separator for the next level of nesting.
+    pub fn split(mut self, separator: &str) -> Self {
+        self.separators.push(separator.to_string());
+        self
+    }
+}
+
+impl<'a, T> Iterator for SplitParsingIterator<'a, T>
+where
+    T: FromSplit,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.line_iterator.next_line()?;
+        let separators =
+            Separators::new(&self.separators, &self.line_iterator.settings().trim_mode);
+
+        match T::from_split(line.text, separators) {
+            Ok(value) => Some(value),
+            Err(error) => panic!(
+                "failed to parse line {} {:?}: {error}",
+                line.index + 1,
+                line.text
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing_line_iterator::ParsingLineIterator;
+
+    #[test]
+    fn it_splits_lines_into_vecs() {
+        let input = "1,2,3,4\n5, 6";
+
+        assert_eq!(
+            vec![vec![1, 2, 3, 4], vec![5, 6]],
+            ParsingLineIterator::<Vec<u32>>::from(input)
+                .split(",")
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_splits_lines_into_tuples_and_arrays() {
+        let input = "2-4,6-8\n2-3,4-5";
+
+        assert_eq!(
+            vec![((2, 4), (6, 8)), ((2, 3), (4, 5))],
+            SplitParsingIterator::<((u32, u32), (u32, u32))>::from(input, ",")
+                .split("-")
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![[[2, 4], [6, 8]], [[2, 3], [4, 5]]],
+            SplitParsingIterator::<[[u32; 2]; 2]>::from(input, ",")
+                .split("-")
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_splits_nested_separators() {
+        let input = "Game 1: 3 blue, 4 red; 1 red\nGame 2: 2 green";
+
+        let games = ParsingLineIterator::<(String, Vec<Vec<(u32, String)>>)>::from(input)
+            .split(": ")
+            .split("; ")
+            .split(", ")
+            .split(" ")
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (
+                    "Game 1".to_string(),
+                    vec![
+                        vec![(3, "blue".to_string()), (4, "red".to_string())],
+                        vec![(1, "red".to_string())]
+                    ]
+                ),
+                ("Game 2".to_string(), vec![vec![(2, "green".to_string())]])
+            ],
+            games
+        );
+    }
+
+    #[test]
+    fn whitespace_separators_split_on_runs_of_whitespace() {
+        assert_eq!(
+            vec![(Parsed(crate::point::Direction4::Right), 4)],
+            SplitParsingIterator::<(Parsed<crate::point::Direction4>, u8)>::from("R    4", " ")
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_trim_mode() {
+        let settings = LineIteratorSettings {
+            trim_mode: TrimMode::None,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![vec![" a".to_string(), "b ".to_string()]],
+            SplitParsingIterator::<Vec<String>>::from_settings(" a,b ", ",", settings)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_reports_wrong_amounts() {
+        assert_eq!(
+            Err("expected 2 parts, found 3 in \"1-2-3\"".to_string()),
+            <(u8, u8)>::from_split("1-2-3", Separators::new(&["-".to_string()], &TrimMode::All))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse line 2 \"1,x\": invalid value \"x\"")]
+    fn it_panics_on_invalid_lines() {
+        SplitParsingIterator::<Vec<u8>>::from("1,2\n1,x", ",").for_each(drop);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::line_iterator::{LineIterator, LineIteratorSettings};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Error for a line that could not be parsed, pointing back to where it is in the input.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseLineError<E> {
+    /// 1-based line number
+    pub line_number: usize,
+    /// Byte offset of the (trimmed) line into the original input
+    pub offset: usize,
+    pub line: String,
+    pub error: E,
+}
+
+impl<E> Display for ParseLineError<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to parse line {} (byte offset {}) {:?}: {:?}",
+            self.line_number, self.offset, self.line, self.error
+        )
+    }
+}
+
+impl<E> Error for ParseLineError<E> where E: Debug {}
+
+/// Like [`crate::parsing_line_iterator::ParsingLineIterator`], but yields a [`Result`] per line
+/// instead of panicking on the first line that fails to parse.
+pub struct TryParsingLineIterator<'a, T> {
+    line_iterator: LineIterator<'a>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> TryParsingLineIterator<'a, T> {
+    pub fn from(input: &'a str) -> Self {
+        Self {
+            line_iterator: LineIterator::from(input),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn from_settings(input: &'a str, settings: LineIteratorSettings) -> Self {
+        Self {
+            line_iterator: LineIterator::from_settings(input, settings),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> TryParsingLineIterator<'a, T>
+where
+    T: FromStr,
+{
+    /// Parse every remaining line, returning either all values or every error that occurred.
+    pub fn collect_all_errors(self) -> Result<Vec<T>, Vec<ParseLineError<T::Err>>> {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in self {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<'a, T> Iterator for TryParsingLineIterator<'a, T>
+where
+    T: FromStr,
+{
+    type Item = Result<T, ParseLineError<T::Err>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.line_iterator.next_line()?;
+
+        Some(line.text.parse::<T>().map_err(|error| ParseLineError {
+            line_number: line.index + 1,
+            offset: line.span.start + (line.text.as_ptr() as usize - line.raw.as_ptr() as usize),
+            line: line.text.to_string(),
+            error,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn it_yields_parsed_values() {
+        let values = TryParsingLineIterator::<i32>::from("1\n-2\n3")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(vec![1, -2, 3], values);
+    }
+
+    #[test]
+    fn it_reports_the_failing_line() {
+        let input = "1\n  two\n3";
+
+        let mut iterator = TryParsingLineIterator::<i32>::from(input);
+
+        assert_eq!(Some(Ok(1)), iterator.next());
+
+        let error = iterator.next().unwrap().unwrap_err();
+        assert_eq!(2, error.line_number);
+        assert_eq!(4, error.offset);
+        assert_eq!("two", error.line);
+        assert_eq!(&input[error.offset..error.offset + 3], "two");
+
+        assert_eq!(Some(Ok(3)), iterator.next());
+        assert_eq!(None, iterator.next());
+    }
+
+    #[test]
+    fn line_numbers_count_skipped_lines() {
+        let settings = LineIteratorSettings {
+            skip_empty: true,
+            ..Default::default()
+        };
+
+        let error = TryParsingLineIterator::<i32>::from_settings("1\n\n\nx", settings)
+            .collect_all_errors()
+            .unwrap_err();
+
+        assert_eq!(4, error[0].line_number);
+        assert_eq!(4, error[0].offset);
+    }
+
+    #[test]
+    fn it_collects_all_errors() {
+        let errors: Vec<ParseLineError<ParseIntError>> =
+            TryParsingLineIterator::<i32>::from("a\n2\nb\n4")
+                .collect_all_errors()
+                .unwrap_err();
+
+        assert_eq!(
+            vec![(1, "a"), (3, "b")],
+            errors
+                .iter()
+                .map(|error| (error.line_number, error.line.as_str()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_collects_all_values_without_errors() {
+        assert_eq!(
+            Ok(vec![1, 2]),
+            TryParsingLineIterator::<u8>::from("1\n2\n").collect_all_errors()
+        );
+    }
+}
@@ -1,12 +1,31 @@
-use std::fmt::{Debug, Formatter};
-
-#[derive(Default, Eq, PartialEq)]
+use crate::line_iterator::{LineIterator, LineIteratorSettings, TrimMode};
+use crate::point::Point;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Index, IndexMut, Range};
+use std::str::FromStr;
+
+#[derive(Clone, Default)]
 pub struct Vec2D<T> {
     inner: Vec<T>,
     positive_width: usize,
     positive_height: usize,
     negative_width: usize,
     negative_height: usize,
+    /// Spare room around the cells left behind by growing, `None` while the cells are packed
+    slack: Option<Slack>,
+}
+
+/// Layout of an inner vec that has room to grow on every side without moving the cells.
+#[derive(Clone, Copy, Debug)]
+struct Slack {
+    /// Length of an allocated row, at least the width of the grid
+    stride: usize,
+    /// Allocated rows above the most negative row
+    row_offset: usize,
+    /// Allocated columns left of the most negative column
+    column_offset: usize,
 }
 
 impl<T> Vec2D<T> {
@@ -24,6 +43,7 @@ impl<T> Vec2D<T> {
             positive_height,
             negative_width,
             negative_height,
+            slack: None,
         }
     }
 
@@ -45,9 +65,24 @@ impl<T> Vec2D<T> {
             positive_height,
             negative_width,
             negative_height,
+            slack: None,
         }
     }
 
+    /// Create a grid with the same extents as `other`, filled with `value`.
+    pub fn new_sized_like<U>(other: &Vec2D<U>, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new_sized_with(
+            other.positive_width,
+            other.positive_height,
+            other.negative_width,
+            other.negative_height,
+            value,
+        )
+    }
+
     pub fn with_capacity(capacity: usize) -> Self {
         Self::with_capacity_and_size(capacity, 0, 0)
     }
@@ -63,6 +98,7 @@ impl<T> Vec2D<T> {
             positive_height,
             negative_width: 0,
             negative_height: 0,
+            slack: None,
         }
     }
 
@@ -73,6 +109,7 @@ impl<T> Vec2D<T> {
             positive_height: height,
             negative_width: 0,
             negative_height: 0,
+            slack: None,
         }
     }
 
@@ -89,6 +126,7 @@ impl<T> Vec2D<T> {
             positive_height: height,
             negative_width,
             negative_height,
+            slack: None,
         }
     }
 
@@ -117,6 +155,7 @@ impl<T> Vec2D<T> {
     }
 
     pub fn push(&mut self, item: T) {
+        self.compact();
         self.inner.push(item);
     }
 
@@ -124,6 +163,7 @@ impl<T> Vec2D<T> {
     where
         I: Iterator<Item = T>,
     {
+        self.compact();
         self.inner.extend(iter);
     }
 
@@ -138,19 +178,35 @@ impl<T> Vec2D<T> {
     }
 
     pub fn at(&self, row: isize, column: isize) -> Option<&T> {
-        self.inner.get(self.to_index(row, column))
+        self.checked_index(row, column)
+            .map(|index| &self.inner[index])
     }
 
     pub fn at_mut(&mut self, row: isize, column: isize) -> Option<&mut T> {
-        let index = self.to_index(row, column);
+        self.checked_index(row, column)
+            .map(|index| &mut self.inner[index])
+    }
+
+    /// Like [`Vec2D::at`], but describes why the position could not be accessed.
+    pub fn try_at(&self, row: isize, column: isize) -> Result<&T, OutOfBoundsError> {
+        self.at(row, column)
+            .ok_or_else(|| self.out_of_bounds(row, column))
+    }
 
-        self.inner.get_mut(index)
+    /// Like [`Vec2D::at_mut`], but describes why the position could not be accessed.
+    pub fn try_at_mut(&mut self, row: isize, column: isize) -> Result<&mut T, OutOfBoundsError> {
+        let error = self.out_of_bounds(row, column);
+
+        self.at_mut(row, column).ok_or(error)
     }
 
     /// Access the indicated position mutably, and grow if it lies outside of the current size.
+    ///
+    /// Growing leaves spare room on the side it grows towards, doubling like [`Vec`] does, so that
+    /// repeatedly growing in the same direction is amortized constant time per cell.
     pub fn growing_at_mut(&mut self, row: isize, column: isize) -> &mut T
     where
-        T: Default + Debug,
+        T: Default,
     {
         // If empty add at least one
         if self.inner.is_empty() {
@@ -158,388 +214,2057 @@ impl<T> Vec2D<T> {
             self.set_size(1, 1);
         }
 
-        // Check if we need to grow in the positive axis
-        if column >= self.positive_width as isize {
-            let missing = (column as usize - self.positive_width) + 1;
+        let grow_up = missing_before(row, self.negative_height);
+        let grow_down = missing_after(row, self.positive_height);
+        let grow_left = missing_before(column, self.negative_width);
+        let grow_right = missing_after(column, self.positive_width);
 
-            // Extend capacity
-            self.inner
-                .reserve(missing * (self.positive_height + self.negative_height));
+        if grow_up + grow_down + grow_left + grow_right > 0 {
+            self.grow(grow_up, grow_down, grow_left, grow_right);
+        }
 
-            for i in 0..(self.positive_height + self.negative_height) {
-                for _ in 0..missing {
-                    self.inner
-                        .insert(self.positive_width * (i + 1) + (i * missing), T::default());
-                }
-            }
+        // Don't need to check, we grow if we're too small
+        self.at_mut_unchecked(row, column)
+    }
+
+    /// Add the given amount of rows and columns on each side, filled with the default value.
+    fn grow(&mut self, up: usize, down: usize, left: usize, right: usize)
+    where
+        T: Default,
+    {
+        let width = self.width();
+        let height = self.height();
 
-            self.positive_width += missing;
+        // Growing assumes every cell is there
+        if self.slack.is_none() && self.inner.len() < width * height {
+            self.inner.resize_with(width * height, T::default);
         }
 
-        if row >= self.positive_height as isize {
-            let missing = (row as usize - self.positive_height) + 1;
+        let (stride, row_offset, column_offset) = self.layout();
+        let allocated_rows = self.inner.len() / stride.max(1);
 
-            // Extend capacity
-            self.inner
-                .reserve(missing * (self.positive_width + self.negative_width));
+        let fits_horizontally = left <= column_offset && right <= stride - column_offset - width;
+        let fits_vertically = up <= row_offset && down <= allocated_rows - row_offset - height;
 
-            for _ in 0..missing {
-                for _ in 0..(self.positive_width + self.negative_width) {
-                    self.push(T::default());
-                }
+        let mut slack = if fits_horizontally && fits_vertically {
+            Slack {
+                stride,
+                row_offset,
+                column_offset,
+            }
+        } else {
+            let (new_stride, new_column_offset) = if fits_horizontally {
+                (stride, column_offset)
+            } else {
+                grown_layout(stride, width, left, right)
+            };
+
+            let (new_allocated_rows, new_row_offset) = if fits_vertically {
+                (allocated_rows, row_offset)
+            } else {
+                grown_layout(allocated_rows, height, up, down)
+            };
+
+            let mut inner = Vec::with_capacity(new_stride * new_allocated_rows);
+            inner.resize_with(new_stride * new_allocated_rows, T::default);
+
+            // Move the existing cells over, leaving the defaults behind
+            for row in 0..height {
+                let from = (row + row_offset) * stride + column_offset;
+                let to = (row + new_row_offset) * new_stride + new_column_offset;
+
+                inner[to..to + width].swap_with_slice(&mut self.inner[from..from + width]);
             }
 
-            self.positive_height += missing;
-        }
+            self.inner = inner;
 
-        // Check if we need to grow in negative axis
-        if column < -(self.negative_width as isize) {
-            let missing = (column + self.negative_width as isize).unsigned_abs();
+            Slack {
+                stride: new_stride,
+                row_offset: new_row_offset,
+                column_offset: new_column_offset,
+            }
+        };
 
-            // Extend capacity
-            self.inner
-                .reserve(missing * (self.positive_height + self.negative_height));
+        slack.row_offset -= up;
+        slack.column_offset -= left;
 
-            // Grow every row
-            for i in (0..self.positive_height + self.negative_height).rev() {
-                let index = i * (self.positive_width + self.negative_width);
+        self.slack = Some(slack);
+        self.negative_height += up;
+        self.positive_height += down;
+        self.negative_width += left;
+        self.positive_width += right;
+    }
 
-                for _ in 0..missing {
-                    self.inner.insert(index, T::default());
-                }
-            }
+    /// Move the cells back together, dropping any spare room left behind by growing.
+    fn compact(&mut self) {
+        let Some(Slack {
+            stride,
+            row_offset,
+            column_offset,
+        }) = self.slack.take()
+        else {
+            return;
+        };
+
+        let width = self.width();
+        let first = row_offset * stride;
+        let last = first + self.height() * stride;
+
+        self.inner = std::mem::take(&mut self.inner)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                (first..last).contains(index)
+                    && (column_offset..column_offset + width).contains(&(index % stride))
+            })
+            .map(|(_, value)| value)
+            .collect();
+    }
+
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.compact();
+        self.positive_width = width;
+        self.positive_height = height;
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.positive_width, self.positive_height)
+    }
+
+    /// Whether the position lies within the positive and negative extents of the grid.
+    pub fn contains(&self, row: isize, column: isize) -> bool {
+        row >= -(self.negative_height as isize)
+            && row < self.positive_height as isize
+            && column >= -(self.negative_width as isize)
+            && column < self.positive_width as isize
+    }
 
-            self.negative_width += missing;
+    /// The amount of rows that hold cells, which can be less than the height while filling up.
+    fn row_count(&self) -> usize {
+        match self.slack {
+            Some(_) => self.height(),
+            None => self.inner.len().div_ceil(self.width().max(1)),
         }
+    }
 
-        if row < -(self.negative_height as isize) {
-            let missing = (row + self.negative_height as isize).unsigned_abs();
+    /// Whether every position within the extents holds a value.
+    fn is_filled(&self) -> bool {
+        self.slack.is_some() || self.inner.len() >= self.width() * self.height()
+    }
 
-            // Extend capacity
-            self.inner
-                .reserve(missing * (self.positive_width + self.negative_width));
+    #[track_caller]
+    pub(crate) fn assert_filled(&self) {
+        assert!(
+            self.is_filled(),
+            "only {} of the {}x{} cells of the grid are filled",
+            self.inner.len(),
+            self.height(),
+            self.width()
+        );
+    }
 
-            // Grow every column, this can always just be pushed in front
-            for _ in 0..self.positive_width + self.negative_width {
-                for _ in 0..missing {
-                    self.inner.insert(0, T::default());
-                }
-            }
+    /// The index of the position, if it lies within the grid and has been filled.
+    fn checked_index(&self, row: isize, column: isize) -> Option<usize> {
+        if !self.contains(row, column) {
+            return None;
+        }
 
-            self.negative_height += missing;
+        let index = self.to_index(row, column);
+
+        (index < self.inner.len()).then_some(index)
+    }
+
+    fn out_of_bounds(&self, row: isize, column: isize) -> OutOfBoundsError {
+        OutOfBoundsError {
+            row,
+            column,
+            rows: -(self.negative_height as isize)..self.positive_height as isize,
+            columns: -(self.negative_width as isize)..self.positive_width as isize,
+            unfilled: self.contains(row, column),
         }
+    }
 
-        // Don't need to check, we grow if we're too small
-        self.at_mut_unchecked(row, column)
+    #[cold]
+    #[track_caller]
+    fn index_out_of_bounds(&self, row: isize, column: isize) -> ! {
+        panic!(
+            "{} (positive_width: {}, positive_height: {}, negative_width: {}, negative_height: {})",
+            self.out_of_bounds(row, column),
+            self.positive_width,
+            self.positive_height,
+            self.negative_width,
+            self.negative_height
+        )
     }
 
-    pub fn set_size(&mut self, width: usize, height: usize) {
-        self.positive_width = width;
-        self.positive_height = height;
+    /// Wrap the position around the edges of the grid, `None` if the grid is empty.
+    fn wrap(&self, row: isize, column: isize) -> Option<(isize, isize)> {
+        let width = self.width() as isize;
+        let height = self.height() as isize;
+
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let negative_width = self.negative_width as isize;
+        let negative_height = self.negative_height as isize;
+
+        Some((
+            (row + negative_height).rem_euclid(height) - negative_height,
+            (column + negative_width).rem_euclid(width) - negative_width,
+        ))
     }
 
-    pub fn size(&self) -> (usize, usize) {
-        (self.positive_width, self.positive_height)
+    /// The row length, and the rows and columns in front of the cells, of the inner vec.
+    #[inline]
+    fn layout(&self) -> (usize, usize, usize) {
+        match self.slack {
+            Some(slack) => (slack.stride, slack.row_offset, slack.column_offset),
+            None => (self.width(), 0, 0),
+        }
+    }
+
+    /// Index of a position counted from the top left cell, ignoring the negative extents.
+    #[inline]
+    fn to_index_unshifted(&self, row: usize, column: usize) -> usize {
+        let (stride, row_offset, column_offset) = self.layout();
+
+        (row + row_offset) * stride + column + column_offset
     }
 
     #[inline]
     fn to_index(&self, row: isize, column: isize) -> usize {
-        let row_adjusted = (row + self.negative_height as isize) as usize;
-        let column_adjusted = (column + self.negative_width as isize) as usize;
+        let (stride, row_offset, column_offset) = self.layout();
+
+        let row_adjusted = (row + self.negative_height as isize) as usize + row_offset;
+        let column_adjusted = (column + self.negative_width as isize) as usize + column_offset;
 
         row_adjusted
-            .saturating_mul(self.positive_width.saturating_add(self.negative_width))
+            .saturating_mul(stride)
             .saturating_add(column_adjusted)
 
         // (row_adjusted * (self.positive_width + self.negative_width)) + column_adjusted
     }
 }
 
-impl<T> IntoIterator for Vec2D<T> {
-    type Item = T;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+/// How many rows or columns are missing in front of the grid to contain the position.
+fn missing_before(position: isize, negative_extent: usize) -> usize {
+    let first = -(negative_extent as isize);
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.inner.into_iter()
+    if position < first {
+        first.abs_diff(position)
+    } else {
+        0
     }
 }
 
-impl<T> Debug for Vec2D<T>
-where
-    T: Debug,
-{
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut inner_format: Vec<String> = Vec::new();
+/// How many rows or columns are missing after the grid to contain the position.
+fn missing_after(position: isize, positive_extent: usize) -> usize {
+    if position >= positive_extent as isize {
+        position.abs_diff(positive_extent as isize) + 1
+    } else {
+        0
+    }
+}
 
-        if !self.inner.is_empty() {
-            for chunk in self.inner.chunks(self.positive_width + self.negative_width) {
-                inner_format.push(format!("{chunk:?}"));
-            }
-        }
+/// New allocated length and offset of the existing cells when `used` has to grow by `before` and
+/// `after`. If the spare room is merely on the wrong side it is spread out over both sides,
+/// otherwise the allocation doubles and the extra room goes to the side(s) being grown.
+fn grown_layout(allocated: usize, used: usize, before: usize, after: usize) -> (usize, usize) {
+    let needed = used + before + after;
 
-        f.debug_struct(&format!("Vec2D<{}>", std::any::type_name::<T>()))
-            .field("width", &self.positive_width)
-            .field("height", &self.positive_height)
-            .field("negative_width", &self.negative_width)
-            .field("negative_height", &self.negative_height)
-            .field("inner", &inner_format)
-            .finish()
+    if needed <= allocated {
+        return (allocated, (allocated - needed) / 2 + before);
     }
+
+    let new_allocated = needed.max(allocated * 2);
+    let extra = new_allocated - needed;
+
+    let extra_before = match (before > 0, after > 0) {
+        (true, true) => extra / 2,
+        (true, false) => extra,
+        _ => 0,
+    };
+
+    (new_allocated, extra_before + before)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<T> Vec2D<T> {
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.rows().flatten()
+    }
 
-    #[test]
-    fn indexing_works() {
-        let vec2d = Vec2D::from(
-            vec![
-                1, 2, 3, // Row 1
-                4, 5, 6, // Row 2
-                7, 8, 9, // Row 3
-            ],
-            3,
-            3,
-        );
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.rows_mut().flatten()
+    }
 
-        assert_eq!((3, 3), vec2d.size());
+    /// Iterate over all cells together with their `(row, column)` position, which can be negative.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> + '_ {
+        let negative_width = self.negative_width as isize;
+        let negative_height = self.negative_height as isize;
+
+        self.rows().enumerate().flat_map(move |(row, cells)| {
+            cells.iter().enumerate().map(move |(column, value)| {
+                (
+                    (
+                        row as isize - negative_height,
+                        column as isize - negative_width,
+                    ),
+                    value,
+                )
+            })
+        })
+    }
 
-        assert_eq!(&1, vec2d.at_unchecked(0, 0));
-        assert_eq!(&3, vec2d.at_unchecked(0, 2));
-        assert_eq!(&4, vec2d.at_unchecked(1, 0));
-        assert_eq!(&8, vec2d.at_unchecked(2, 1));
+    pub fn indexed_iter_mut(&mut self) -> impl Iterator<Item = ((isize, isize), &mut T)> + '_ {
+        let negative_width = self.negative_width as isize;
+        let negative_height = self.negative_height as isize;
+
+        self.rows_mut().enumerate().flat_map(move |(row, cells)| {
+            cells.iter_mut().enumerate().map(move |(column, value)| {
+                (
+                    (
+                        row as isize - negative_height,
+                        column as isize - negative_width,
+                    ),
+                    value,
+                )
+            })
+        })
     }
 
-    mod growing {
-        use super::*;
+    /// Iterate over the rows from top to bottom, starting at the most negative row.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        let (stride, row_offset, column_offset) = self.layout();
+        let width = self.width();
 
-        #[test]
-        fn growing_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::default();
+        self.inner
+            .chunks(stride.max(1))
+            .skip(row_offset)
+            .take(self.row_count())
+            .map(move |chunk| &chunk[column_offset..(column_offset + width).min(chunk.len())])
+    }
 
-            *vec2d.growing_at_mut(0, 0) = 1;
-            let expected = Vec2D::from(vec![1], 1, 1);
-            assert_eq!(expected, vec2d);
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        let (stride, row_offset, column_offset) = self.layout();
+        let width = self.width();
+        let row_count = self.row_count();
 
-            *vec2d.growing_at_mut(2, 2) = 2;
-            let expected = Vec2D::from(
-                vec![
-                    1, 0, 0, //
-                    0, 0, 0, //
-                    0, 0, 2, //
-                ],
-                3,
-                3,
-            );
-            assert_eq!(expected, vec2d);
+        self.inner
+            .chunks_mut(stride.max(1))
+            .skip(row_offset)
+            .take(row_count)
+            .map(move |chunk| {
+                let end = (column_offset + width).min(chunk.len());
 
-            *vec2d.growing_at_mut(2, 4) = 3;
-            let expected = Vec2D::from(
-                vec![
-                    1, 0, 0, 0, 0, //
-                    0, 0, 0, 0, 0, //
-                    0, 0, 2, 0, 3, //
-                ],
-                5,
-                3,
-            );
-            assert_eq!(expected, vec2d);
+                &mut chunk[column_offset..end]
+            })
+    }
 
-            *vec2d.growing_at_mut(4, 1) = 4;
-            let expected = Vec2D::from(
-                vec![
-                    1, 0, 0, 0, 0, //
-                    0, 0, 0, 0, 0, //
-                    0, 0, 2, 0, 3, //
-                    0, 0, 0, 0, 0, //
-                    0, 4, 0, 0, 0, //
-                ],
-                5,
-                5,
-            );
-            assert_eq!(expected, vec2d);
-        }
+    /// Iterate over the columns from left to right, starting at the most negative column. Every
+    /// column is an iterator over its cells from top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width()).map(move |column| self.rows().filter_map(move |row| row.get(column)))
+    }
 
-        // TODO: Grow with negative width and positive height
-        // TODO: Grow with negative width and negative height
+    /// Find the `(row, column)` positions of all cells matching the predicate.
+    pub fn positions_where<'a, P>(
+        &'a self,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (isize, isize)> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.indexed_iter()
+            .filter_map(move |(position, value)| predicate(value).then_some(position))
+    }
+}
 
-        #[test]
-        fn growing_width_with_negative_height_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
-                vec![
-                    1, 2, //
-                    3, 4, //
-                    5, 6, //
-                ],
-                2,
-                1,
-                0,
-                2,
-            );
+/// Transformations rearrange the picture formed by the cells. The extents follow the shape of the
+/// picture: flips keep them, while transposing and rotating by 90 degrees swap the row extents with
+/// the column extents (`negative_height` becomes `negative_width` and so on). This means a grid
+/// without negative extents never gains any.
+///
+/// Every transformation panics if the grid is still being filled with [`Vec2D::push`] and not
+/// every cell within its extents holds a value yet.
+impl<T> Vec2D<T> {
+    pub fn transpose(mut self) -> Self {
+        self.transpose_in_place();
+        self
+    }
 
-            *vec2d.growing_at_mut(-2, 3) = 9;
+    pub fn rotate_cw(mut self) -> Self {
+        self.rotate_cw_in_place();
+        self
+    }
 
-            let expected = Vec2D::from_negative(
-                vec![
-                    1, 2, 0, 9, //
-                    3, 4, 0, 0, //
-                    5, 6, 0, 0, //
-                ],
-                4,
-                1,
-                0,
-                2,
-            );
-            assert_eq!(expected, vec2d);
-        }
+    pub fn rotate_ccw(mut self) -> Self {
+        self.rotate_ccw_in_place();
+        self
+    }
 
-        #[test]
-        fn growing_width_with_negative_width_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(vec![1, 0], 1, 1, 1, 0);
+    pub fn rotate_180(mut self) -> Self {
+        self.rotate_180_in_place();
+        self
+    }
 
-            *vec2d.growing_at_mut(0, 3) = 9;
+    /// Mirror the grid left to right.
+    pub fn flip_horizontal(mut self) -> Self {
+        self.flip_horizontal_in_place();
+        self
+    }
 
-            let expected = Vec2D::from_negative(vec![1, 0, 0, 0, 9], 4, 1, 1, 0);
-            assert_eq!(expected, vec2d);
-        }
+    /// Mirror the grid top to bottom.
+    pub fn flip_vertical(mut self) -> Self {
+        self.flip_vertical_in_place();
+        self
+    }
 
-        #[test]
-        fn growing_to_negative_width_1_height_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from(vec![1], 1, 1);
+    /// Swap rows and columns, square grids are transposed without moving the cells to a new vec.
+    pub fn transpose_in_place(&mut self) {
+        self.assert_filled();
 
-            *vec2d.growing_at_mut(0, -4) = 9;
+        let width = self.width();
+        let height = self.height();
 
-            let expected = Vec2D::from_negative(vec![9, 0, 0, 0, 1], 1, 1, 4, 0);
-            assert_eq!(expected, vec2d);
+        if width == height {
+            for row in 0..height {
+                for column in row + 1..width {
+                    let a = self.to_index_unshifted(row, column);
+                    let b = self.to_index_unshifted(column, row);
+
+                    self.inner.swap(a, b);
+                }
+            }
+        } else {
+            self.compact();
+
+            let mut cells = std::mem::take(&mut self.inner)
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>();
+
+            self.inner = (0..width)
+                .flat_map(|column| (0..height).map(move |row| row * width + column))
+                .map(|index| cells[index].take().unwrap())
+                .collect();
         }
 
-        #[test]
-        fn growing_to_negative_width_3_height_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from(
-                vec![
-                    1, 2, //
-                    3, 4, //
-                    5, 6, //
-                ],
-                2,
-                3,
-            );
+        std::mem::swap(&mut self.positive_width, &mut self.positive_height);
+        std::mem::swap(&mut self.negative_width, &mut self.negative_height);
+    }
 
-            *vec2d.growing_at_mut(1, -4) = 9;
+    pub fn rotate_cw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_horizontal_in_place();
+    }
 
-            let expected = Vec2D::from_negative(
-                vec![
-                    0, 0, 0, 0, 1, 2, //
-                    9, 0, 0, 0, 3, 4, //
-                    0, 0, 0, 0, 5, 6, //
-                ],
-                2,
-                3,
-                4,
-                0,
-            );
-            assert_eq!(expected, vec2d);
-        }
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.transpose_in_place();
+        self.flip_vertical_in_place();
+    }
 
-        #[test]
-        fn growing_to_negative_width_with_negative_height() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
-                vec![
-                    1, 2, //
-                    3, 4, //
-                ],
-                2,
+    pub fn rotate_180_in_place(&mut self) {
+        self.flip_horizontal_in_place();
+        self.flip_vertical_in_place();
+    }
+
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.assert_filled();
+        self.rows_mut().for_each(|row| row.reverse());
+    }
+
+    pub fn flip_vertical_in_place(&mut self) {
+        self.assert_filled();
+
+        let width = self.width();
+        let height = self.height();
+
+        for row in 0..height / 2 {
+            for column in 0..width {
+                let a = self.to_index_unshifted(row, column);
+                let b = self.to_index_unshifted(height - 1 - row, column);
+
+                self.inner.swap(a, b);
+            }
+        }
+    }
+
+    /// Iterate over all 8 rotations and reflections of the grid, starting with the grid itself,
+    /// followed by its clockwise rotations and then the clockwise rotations of its mirror image.
+    pub fn orientations(&self) -> impl Iterator<Item = Vec2D<T>>
+    where
+        T: Clone,
+    {
+        (0..8).scan(self.clone(), |grid, i| {
+            if i == 4 {
+                grid.flip_horizontal_in_place();
+            }
+
+            let orientation = grid.clone();
+            grid.rotate_cw_in_place();
+
+            Some(orientation)
+        })
+    }
+}
+
+/// Error for accessing a position outside of the grid, or a cell that has not been filled yet.
+#[derive(Debug, Eq, PartialEq)]
+pub struct OutOfBoundsError {
+    pub row: isize,
+    pub column: isize,
+    /// The valid rows of the grid, including the negative ones
+    pub rows: Range<isize>,
+    /// The valid columns of the grid, including the negative ones
+    pub columns: Range<isize>,
+    /// The position lies within the grid, but its cell has not been pushed yet
+    pub unfilled: bool,
+}
+
+impl Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.unfilled {
+            return write!(
+                f,
+                "position (row {}, column {}) has not been filled yet",
+                self.row, self.column
+            );
+        }
+
+        write!(
+            f,
+            "position (row {}, column {}) is out of bounds for rows {:?} and columns {:?}",
+            self.row, self.column, self.rows, self.columns
+        )
+    }
+}
+
+impl Error for OutOfBoundsError {}
+
+/// Offsets of the 4 orthogonal neighbours as `(row, column)`, clockwise starting at the top.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of all 8 surrounding neighbours as `(row, column)`, row by row.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl<T> Vec2D<T> {
+    pub fn neighbours4(
+        &self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &T)> + '_ {
+        self.neighbours_with(row, column, &NEIGHBOURS4)
+    }
+
+    pub fn neighbours8(
+        &self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &T)> + '_ {
+        self.neighbours_with(row, column, &NEIGHBOURS8)
+    }
+
+    /// Iterate over the cells at the given `(row, column)` offsets from the position, skipping
+    /// those that lie outside of the grid.
+    pub fn neighbours_with<'a>(
+        &'a self,
+        row: isize,
+        column: isize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (isize, isize, &'a T)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(row_offset, column_offset)| {
+                let row = row.checked_add(row_offset)?;
+                let column = column.checked_add(column_offset)?;
+
+                self.checked_index(row, column)
+                    .map(|index| (row, column, &self.inner[index]))
+            })
+    }
+
+    pub fn neighbours4_mut(
+        &mut self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        self.neighbours_with_mut(row, column, &NEIGHBOURS4)
+    }
+
+    pub fn neighbours8_mut(
+        &mut self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        self.neighbours_with_mut(row, column, &NEIGHBOURS8)
+    }
+
+    /// Mutable version of [`Vec2D::neighbours_with`], offsets pointing at the same cell are only
+    /// yielded once.
+    pub fn neighbours_with_mut(
+        &mut self,
+        row: isize,
+        column: isize,
+        offsets: &[(isize, isize)],
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        let positions = offsets
+            .iter()
+            .filter_map(|&(row_offset, column_offset)| {
+                let row = row.checked_add(row_offset)?;
+                let column = column.checked_add(column_offset)?;
+
+                self.checked_index(row, column)
+                    .map(|index| (row, column, index))
+            })
+            .collect::<Vec<_>>();
+
+        self.disjoint_mut(positions)
+    }
+
+    pub fn neighbours4_wrapping(
+        &self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &T)> + '_ {
+        self.neighbours_with_wrapping(row, column, &NEIGHBOURS4)
+    }
+
+    pub fn neighbours8_wrapping(
+        &self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &T)> + '_ {
+        self.neighbours_with_wrapping(row, column, &NEIGHBOURS8)
+    }
+
+    /// Like [`Vec2D::neighbours_with`], but treats the grid as a torus: positions falling off one
+    /// edge wrap around to the other side. The yielded positions are the wrapped ones.
+    pub fn neighbours_with_wrapping<'a>(
+        &'a self,
+        row: isize,
+        column: isize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (isize, isize, &'a T)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(row_offset, column_offset)| {
+                let (row, column) = self.wrap(
+                    row.wrapping_add(row_offset),
+                    column.wrapping_add(column_offset),
+                )?;
+
+                self.checked_index(row, column)
+                    .map(|index| (row, column, &self.inner[index]))
+            })
+    }
+
+    pub fn neighbours4_wrapping_mut(
+        &mut self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        self.neighbours_with_wrapping_mut(row, column, &NEIGHBOURS4)
+    }
+
+    pub fn neighbours8_wrapping_mut(
+        &mut self,
+        row: isize,
+        column: isize,
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        self.neighbours_with_wrapping_mut(row, column, &NEIGHBOURS8)
+    }
+
+    /// Mutable version of [`Vec2D::neighbours_with_wrapping`], offsets wrapping onto the same
+    /// cell are only yielded once.
+    pub fn neighbours_with_wrapping_mut(
+        &mut self,
+        row: isize,
+        column: isize,
+        offsets: &[(isize, isize)],
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        let positions = offsets
+            .iter()
+            .filter_map(|&(row_offset, column_offset)| {
+                let (row, column) = self.wrap(
+                    row.wrapping_add(row_offset),
+                    column.wrapping_add(column_offset),
+                )?;
+
+                self.checked_index(row, column)
+                    .map(|index| (row, column, index))
+            })
+            .collect::<Vec<_>>();
+
+        self.disjoint_mut(positions)
+    }
+
+    /// Hand out mutable references to the given `(row, column, index)` positions in their original
+    /// order, dropping duplicate indices.
+    fn disjoint_mut(
+        &mut self,
+        mut positions: Vec<(isize, isize, usize)>,
+    ) -> impl Iterator<Item = (isize, isize, &mut T)> + '_ {
+        let mut seen = Vec::with_capacity(positions.len());
+        positions.retain(|&(_, _, index)| {
+            if seen.contains(&index) {
+                return false;
+            }
+
+            seen.push(index);
+            true
+        });
+
+        // Sort by index so we can split the slice in order, remembering the original order
+        let mut order = (0..positions.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| positions[i].2);
+
+        let mut references = Vec::with_capacity(positions.len());
+        let mut rest = self.inner.as_mut_slice();
+        let mut consumed = 0;
+
+        for i in order {
+            let (row, column, index) = positions[i];
+            let (_, tail) = std::mem::take(&mut rest).split_at_mut(index - consumed);
+            let (cell, tail) = tail.split_first_mut().unwrap();
+
+            references.push((i, row, column, cell));
+            rest = tail;
+            consumed = index + 1;
+        }
+
+        references.sort_by_key(|&(i, ..)| i);
+
+        references
+            .into_iter()
+            .map(|(_, row, column, cell)| (row, column, cell))
+    }
+}
+
+/// Positions of marker characters found while parsing a grid, as `(row, column)`.
+pub type GridMarkers = HashMap<char, Vec<(isize, isize)>>;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseGridError {
+    /// A row did not have the same length as the first row, rows are 0-based
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be converted into a cell
+    InvalidCharacter {
+        row: usize,
+        column: usize,
+        character: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row"
+            ),
+            ParseGridError::InvalidCharacter {
+                row,
+                column,
+                character,
+            } => write!(
+                f,
+                "invalid character {character:?} at row {row}, column {column}"
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+impl<T> Vec2D<T> {
+    /// Parse a grid of characters, converting every character into a cell using `f`.
+    ///
+    /// Width and height are taken from the input, every row must be as wide as the first one.
+    pub fn parse_grid<F>(input: &str, mut f: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> T,
+    {
+        Self::try_parse_grid(input, &[], |c| Some(f(c))).map(|(vec2d, _)| vec2d)
+    }
+
+    /// Like [`Vec2D::parse_grid`], but also records where each of the `markers` characters was
+    /// found. The marker characters themselves are still passed to `f`.
+    pub fn parse_grid_with_markers<F>(
+        input: &str,
+        markers: &[char],
+        mut f: F,
+    ) -> Result<(Self, GridMarkers), ParseGridError>
+    where
+        F: FnMut(char) -> T,
+    {
+        Self::try_parse_grid(input, markers, |c| Some(f(c)))
+    }
+
+    fn try_parse_grid<F>(
+        input: &str,
+        markers: &[char],
+        mut f: F,
+    ) -> Result<(Self, GridMarkers), ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let input = input.trim_end_matches(['\r', '\n']);
+
+        let mut inner = Vec::with_capacity(input.len());
+        let mut found_markers = GridMarkers::new();
+        let mut width = None;
+        let mut height = 0;
+
+        if !input.is_empty() {
+            let settings = LineIteratorSettings {
+                trim_mode: TrimMode::LineEndOnly,
+                ..Default::default()
+            };
+
+            for (row, line) in LineIterator::from_settings(input, settings).enumerate() {
+                let mut found = 0;
+
+                for (column, character) in line.chars().enumerate() {
+                    if markers.contains(&character) {
+                        found_markers
+                            .entry(character)
+                            .or_default()
+                            .push((row as isize, column as isize));
+                    }
+
+                    inner.push(f(character).ok_or(ParseGridError::InvalidCharacter {
+                        row,
+                        column,
+                        character,
+                    })?);
+
+                    found += 1;
+                }
+
+                let expected = *width.get_or_insert(found);
+
+                if found != expected {
+                    return Err(ParseGridError::RaggedRow {
+                        row,
+                        expected,
+                        found,
+                    });
+                }
+
+                height += 1;
+            }
+        }
+
+        Ok((Self::from(inner, width.unwrap_or(0), height), found_markers))
+    }
+}
+
+impl FromStr for Vec2D<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_grid(s, |c| c)
+    }
+}
+
+impl FromStr for Vec2D<u8> {
+    type Err = ParseGridError;
+
+    /// Parse a grid of single digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse_grid(s, &[], |c| c.to_digit(10).map(|digit| digit as u8))
+            .map(|(vec2d, _)| vec2d)
+    }
+}
+
+impl FromStr for Vec2D<u32> {
+    type Err = ParseGridError;
+
+    /// Parse a grid of single digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse_grid(s, &[], |c| c.to_digit(10)).map(|(vec2d, _)| vec2d)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RenderSettings {
+    /// Print the row numbers in front of, and the column numbers above the grid
+    pub axis_labels: bool,
+    /// Characters drawn over the cells at the given `(row, column)` positions, like a path
+    pub overlay: HashMap<(isize, isize), char>,
+}
+
+impl<T> Vec2D<T> {
+    /// Draw the grid as a picture, converting every cell into a character using `f`.
+    pub fn render_with<F>(&self, f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        self.render_with_settings(f, &RenderSettings::default())
+    }
+
+    pub fn render_with_settings<F>(&self, f: F, settings: &RenderSettings) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut output = String::new();
+
+        self.write_rendered(&mut output, f, settings)
+            .expect("writing to a String does not fail");
+
+        output
+    }
+
+    fn write_rendered<W, F>(
+        &self,
+        output: &mut W,
+        mut f: F,
+        settings: &RenderSettings,
+    ) -> std::fmt::Result
+    where
+        W: std::fmt::Write,
+        F: FnMut(&T) -> char,
+    {
+        let columns = -(self.negative_width as isize)..self.positive_width as isize;
+        let rows = -(self.negative_height as isize)..self.positive_height as isize;
+
+        let row_label_width = if settings.axis_labels {
+            rows.clone()
+                .map(|row| row.to_string().len())
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        let mut lines = Vec::new();
+
+        if settings.axis_labels && !columns.is_empty() {
+            // Column numbers are written top to bottom, right aligned
+            let labels = columns
+                .clone()
+                .map(|column| column.to_string())
+                .collect::<Vec<_>>();
+            let label_height = labels.iter().map(String::len).max().unwrap_or(0);
+
+            for line in 0..label_height {
+                let mut text = " ".repeat(row_label_width + 1);
+
+                for label in &labels {
+                    let padding = label_height - label.len();
+                    text.push(
+                        line.checked_sub(padding)
+                            .and_then(|index| label.chars().nth(index))
+                            .unwrap_or(' '),
+                    );
+                }
+
+                lines.push(text.trim_end().to_string());
+            }
+        }
+
+        for (row, cells) in rows.zip(self.rows()) {
+            let mut text = String::with_capacity(row_label_width + 1 + cells.len());
+
+            if settings.axis_labels {
+                text.push_str(&format!("{row:>row_label_width$} "));
+            }
+
+            for (column, cell) in columns.clone().zip(cells) {
+                text.push(
+                    settings
+                        .overlay
+                        .get(&(row, column))
+                        .copied()
+                        .unwrap_or_else(|| f(cell)),
+                );
+            }
+
+            lines.push(text);
+        }
+
+        write!(output, "{}", lines.join("\n"))
+    }
+}
+
+impl Display for Vec2D<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_rendered(f, |c| *c, &RenderSettings::default())
+    }
+}
+
+impl Display for Vec2D<bool> {
+    /// Draws `true` as `#` and `false` as `.`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_rendered(
+            f,
+            |b| if *b { '#' } else { '.' },
+            &RenderSettings::default(),
+        )
+    }
+}
+
+impl<T> Index<(isize, isize)> for Vec2D<T> {
+    type Output = T;
+
+    /// Panics with the grid extents if the `(row, column)` position lies outside of the grid.
+    fn index(&self, (row, column): (isize, isize)) -> &Self::Output {
+        match self.checked_index(row, column) {
+            Some(index) => &self.inner[index],
+            None => self.index_out_of_bounds(row, column),
+        }
+    }
+}
+
+impl<T> IndexMut<(isize, isize)> for Vec2D<T> {
+    fn index_mut(&mut self, (row, column): (isize, isize)) -> &mut Self::Output {
+        match self.checked_index(row, column) {
+            Some(index) => &mut self.inner[index],
+            None => self.index_out_of_bounds(row, column),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Vec2D<T> {
+    type Output = T;
+
+    /// Same as indexing with `(isize, isize)`, so only reaches the non-negative positions.
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self[(saturating_isize(row), saturating_isize(column))]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Vec2D<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self[(saturating_isize(row), saturating_isize(column))]
+    }
+}
+
+impl<T> Index<Point> for Vec2D<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        &self[(point.row, point.column)]
+    }
+}
+
+impl<T> IndexMut<Point> for Vec2D<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        &mut self[(point.row, point.column)]
+    }
+}
+
+fn saturating_isize(value: usize) -> isize {
+    isize::try_from(value).unwrap_or(isize::MAX)
+}
+
+impl<T> IntoIterator for Vec2D<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.compact();
+        self.inner.into_iter()
+    }
+}
+
+impl<T> PartialEq for Vec2D<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.positive_width == other.positive_width
+            && self.positive_height == other.positive_height
+            && self.negative_width == other.negative_width
+            && self.negative_height == other.negative_height
+            && self.rows().eq(other.rows())
+    }
+}
+
+impl<T> Eq for Vec2D<T> where T: Eq {}
+
+impl<T> Debug for Vec2D<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let inner_format = self
+            .rows()
+            .map(|row| format!("{row:?}"))
+            .collect::<Vec<_>>();
+
+        f.debug_struct(&format!("Vec2D<{}>", std::any::type_name::<T>()))
+            .field("width", &self.positive_width)
+            .field("height", &self.positive_height)
+            .field("negative_width", &self.negative_width)
+            .field("negative_height", &self.negative_height)
+            .field("inner", &inner_format)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexing_works() {
+        let vec2d = Vec2D::from(
+            vec![
+                1, 2, 3, // Row 1
+                4, 5, 6, // Row 2
+                7, 8, 9, // Row 3
+            ],
+            3,
+            3,
+        );
+
+        assert_eq!((3, 3), vec2d.size());
+
+        assert_eq!(&1, vec2d.at_unchecked(0, 0));
+        assert_eq!(&3, vec2d.at_unchecked(0, 2));
+        assert_eq!(&4, vec2d.at_unchecked(1, 0));
+        assert_eq!(&8, vec2d.at_unchecked(2, 1));
+    }
+
+    mod growing {
+        use super::*;
+
+        #[test]
+        fn growing_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::default();
+
+            *vec2d.growing_at_mut(0, 0) = 1;
+            let expected = Vec2D::from(vec![1], 1, 1);
+            assert_eq!(expected, vec2d);
+
+            *vec2d.growing_at_mut(2, 2) = 2;
+            let expected = Vec2D::from(
+                vec![
+                    1, 0, 0, //
+                    0, 0, 0, //
+                    0, 0, 2, //
+                ],
+                3,
+                3,
+            );
+            assert_eq!(expected, vec2d);
+
+            *vec2d.growing_at_mut(2, 4) = 3;
+            let expected = Vec2D::from(
+                vec![
+                    1, 0, 0, 0, 0, //
+                    0, 0, 0, 0, 0, //
+                    0, 0, 2, 0, 3, //
+                ],
+                5,
+                3,
+            );
+            assert_eq!(expected, vec2d);
+
+            *vec2d.growing_at_mut(4, 1) = 4;
+            let expected = Vec2D::from(
+                vec![
+                    1, 0, 0, 0, 0, //
+                    0, 0, 0, 0, 0, //
+                    0, 0, 2, 0, 3, //
+                    0, 0, 0, 0, 0, //
+                    0, 4, 0, 0, 0, //
+                ],
+                5,
+                5,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_one_step_at_a_time_keeps_values() {
+            let mut vec2d: Vec2D<isize> = Vec2D::default();
+
+            for i in 0..50 {
+                *vec2d.growing_at_mut(i, i) = i;
+                *vec2d.growing_at_mut(-i, -i) = -i;
+                *vec2d.growing_at_mut(i, -i) = 1000 + i;
+                *vec2d.growing_at_mut(-i, i) = 2000 + i;
+            }
+
+            assert_eq!((50, 50), vec2d.size());
+            assert_eq!(49, vec2d.negative_width());
+            assert_eq!(49, vec2d.negative_height());
+
+            for i in 1..50 {
+                assert_eq!(Some(&i), vec2d.at(i, i));
+                assert_eq!(Some(&-i), vec2d.at(-i, -i));
+                assert_eq!(Some(&(1000 + i)), vec2d.at(i, -i));
+                assert_eq!(Some(&(2000 + i)), vec2d.at(-i, i));
+            }
+
+            assert_eq!(Some(&0), vec2d.at(3, 7));
+            assert_eq!(None, vec2d.at(0, 50));
+            assert_eq!(99 * 99, vec2d.iter().count());
+        }
+
+        #[test]
+        fn growing_one_step_at_a_time_reuses_slack() {
+            let mut vec2d: Vec2D<i32> = Vec2D::default();
+            let mut reallocations = 0;
+            let mut last_stride = 0;
+
+            for column in 0..1000 {
+                *vec2d.growing_at_mut(0, -column) = 1;
+
+                let (stride, _, _) = vec2d.layout();
+                if stride != last_stride {
+                    reallocations += 1;
+                    last_stride = stride;
+                }
+            }
+
+            assert!(reallocations <= 11, "reallocated {reallocations} times");
+            assert_eq!(Vec2D::from_negative(vec![1; 1000], 1, 1, 999, 0), vec2d);
+        }
+
+        #[test]
+        fn grown_grid_can_be_consumed_and_extended() {
+            let mut vec2d = Vec2D::from(vec![1, 2], 2, 1);
+
+            *vec2d.growing_at_mut(-1, -1) = 9;
+
+            assert_eq!(
+                "Vec2D<i32> { width: 2, height: 1, negative_width: 1, negative_height: 1, inner: [\"[9, 0, 0]\", \"[0, 1, 2]\"] }",
+                format!("{vec2d:?}")
+            );
+            assert_eq!(
+                vec![9, 0, 0, 0, 1, 2],
+                vec2d.into_iter().collect::<Vec<_>>()
+            );
+
+            let mut vec2d = Vec2D::from(vec![1, 2], 2, 1);
+            *vec2d.growing_at_mut(0, 2) = 3;
+            vec2d.extend([4, 5, 6].into_iter());
+            vec2d.set_size(3, 2);
+
+            assert_eq!(Vec2D::from(vec![1, 2, 3, 4, 5, 6], 3, 2), vec2d);
+        }
+
+        // TODO: Grow with negative width and positive height
+        // TODO: Grow with negative width and negative height
+
+        #[test]
+        fn growing_width_with_negative_height_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+                vec![
+                    1, 2, //
+                    3, 4, //
+                    5, 6, //
+                ],
+                2,
+                1,
+                0,
+                2,
+            );
+
+            *vec2d.growing_at_mut(-2, 3) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    1, 2, 0, 9, //
+                    3, 4, 0, 0, //
+                    5, 6, 0, 0, //
+                ],
+                4,
+                1,
+                0,
+                2,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_width_with_negative_width_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(vec![1, 0], 1, 1, 1, 0);
+
+            *vec2d.growing_at_mut(0, 3) = 9;
+
+            let expected = Vec2D::from_negative(vec![1, 0, 0, 0, 9], 4, 1, 1, 0);
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_to_negative_width_1_height_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from(vec![1], 1, 1);
+
+            *vec2d.growing_at_mut(0, -4) = 9;
+
+            let expected = Vec2D::from_negative(vec![9, 0, 0, 0, 1], 1, 1, 4, 0);
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_to_negative_width_3_height_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from(
+                vec![
+                    1, 2, //
+                    3, 4, //
+                    5, 6, //
+                ],
+                2,
+                3,
+            );
+
+            *vec2d.growing_at_mut(1, -4) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    0, 0, 0, 0, 1, 2, //
+                    9, 0, 0, 0, 3, 4, //
+                    0, 0, 0, 0, 5, 6, //
+                ],
+                2,
+                3,
+                4,
+                0,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_to_negative_width_with_negative_height() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+                vec![
+                    1, 2, //
+                    3, 4, //
+                ],
+                2,
+                1,
+                0,
+                1,
+            );
+
+            *vec2d.growing_at_mut(0, -2) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    0, 0, 1, 2, //
+                    9, 0, 3, 4, //
+                ],
+                2,
+                1,
+                2,
+                1,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_height_with_negative_height_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+                vec![
+                    4, //
+                    3, //
+                    2, //
+                    1, //
+                    0, //
+                ],
+                1,
+                1,
+                0,
+                4,
+            );
+
+            *vec2d.growing_at_mut(3, 0) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    4, //
+                    3, //
+                    2, //
+                    1, //
+                    0, // 0, 0
+                    0, //
+                    0, //
+                    9, //
+                ],
+                1,
+                4,
+                0,
+                4,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_to_negative_height_1_width() {
+            let mut vec2d = Vec2D::from(
+                vec![
+                    1, //
+                    2, //
+                ],
+                1,
+                2,
+            );
+
+            *vec2d.growing_at_mut(-3, 0) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    9, //,
+                    0, //
+                    0, //
+                    1, //
+                    2, //
+                ],
+                1,
+                2,
+                0,
+                3,
+            );
+
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_to_negative_height_3_width() {
+            let mut vec2d = Vec2D::from(
+                vec![
+                    1, 2, 3, //
+                    4, 5, 6, //
+                    7, 8, 9, //
+                ],
+                3,
+                3,
+            );
+
+            *vec2d.growing_at_mut(-2, 2) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    0, 0, 9, //
+                    0, 0, 0, //
+                    1, 2, 3, //
+                    4, 5, 6, //
+                    7, 8, 9, //
+                ],
+                3,
+                3,
+                0,
+                2,
+            );
+
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_to_negative_height_with_negative_width() {
+            let mut vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, //
+                    3, 4, //
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            *vec2d.growing_at_mut(-3, -1) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    9, 0, //
+                    0, 0, //
+                    1, 2, //
+                    3, 4, //
+                ],
+                1,
+                1,
+                1,
+                3,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_height_with_negative_width_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, //
+                ],
+                1,
+                1,
+                2,
+                0,
+            );
+
+            *vec2d.growing_at_mut(2, -1) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, //
+                    0, 0, 0, //
+                    0, 9, 0, //
+                ],
                 1,
+                3,
+                2,
                 0,
+            );
+            assert_eq!(expected, vec2d);
+        }
+
+        #[test]
+        fn growing_height_with_negative_width_and_negative_height_works() {
+            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+                vec![
+                    1, 2, //
+                    3, 4, //
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            *vec2d.growing_at_mut(2, 0) = 9;
+
+            let expected = Vec2D::from_negative(
+                vec![
+                    1, 2, //
+                    3, 4, //
+                    0, 0, //
+                    0, 9, //
+                ],
+                1,
+                3,
+                1,
+                1,
+            );
+            assert_eq!(expected, vec2d);
+        }
+    }
+
+    mod bounds {
+        use super::*;
+
+        #[test]
+        fn at_works_in_every_quadrant() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    'A', 'B', // Row -1
+                    'C', 'D', // Row 0
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            assert_eq!(Some(&'A'), vec2d.at(-1, -1));
+            assert_eq!(Some(&'B'), vec2d.at(-1, 0));
+            assert_eq!(Some(&'C'), vec2d.at(0, -1));
+            assert_eq!(Some(&'D'), vec2d.at(0, 0));
+        }
+
+        #[test]
+        fn at_rejects_positions_past_every_edge() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    'A', 'B', // Row -1
+                    'C', 'D', // Row 0
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            // Past the positive width, would otherwise wrap into the next row
+            assert_eq!(None, vec2d.at(-1, 1));
+            // Past the negative width, would otherwise wrap into the previous row
+            assert_eq!(None, vec2d.at(0, -2));
+            // Past the positive height
+            assert_eq!(None, vec2d.at(1, 0));
+            // Past the negative height
+            assert_eq!(None, vec2d.at(-2, -1));
+            assert_eq!(None, vec2d.at(isize::MIN, isize::MIN));
+            assert_eq!(None, vec2d.at(isize::MAX, isize::MAX));
+        }
+
+        #[test]
+        fn at_does_not_wrap_into_the_next_row() {
+            let vec2d = Vec2D::from(vec![1, 2, 3, 4], 2, 2);
+
+            assert_eq!(None, vec2d.at(0, 2));
+        }
+
+        #[test]
+        fn at_mut_works_in_every_quadrant() {
+            let mut vec2d = Vec2D::from_negative(
+                vec![
+                    'A', 'B', // Row -1
+                    'C', 'D', // Row 0
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            *vec2d.at_mut(-1, -1).unwrap() = 'a';
+            *vec2d.at_mut(-1, 0).unwrap() = 'b';
+            *vec2d.at_mut(0, -1).unwrap() = 'c';
+            *vec2d.at_mut(0, 0).unwrap() = 'd';
+
+            assert_eq!(
+                Vec2D::from_negative(vec!['a', 'b', 'c', 'd'], 1, 1, 1, 1),
+                vec2d
+            );
+
+            assert_eq!(None, vec2d.at_mut(-1, 1));
+            assert_eq!(None, vec2d.at_mut(1, -1));
+        }
+
+        #[test]
+        fn contains_works() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    'A', 'B', // Row -1
+                    'C', 'D', // Row 0
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            assert!(vec2d.contains(-1, -1));
+            assert!(vec2d.contains(0, 0));
+            assert!(!vec2d.contains(-2, 0));
+            assert!(!vec2d.contains(0, 1));
+        }
+
+        #[test]
+        fn try_at_describes_the_error() {
+            let mut vec2d = Vec2D::from_negative(
+                vec![
+                    'A', 'B', // Row -1
+                    'C', 'D', // Row 0
+                ],
+                1,
+                1,
+                1,
+                1,
+            );
+
+            assert_eq!(Ok(&'C'), vec2d.try_at(0, -1));
+
+            let error = vec2d.try_at(2, -3).unwrap_err();
+            assert_eq!(
+                OutOfBoundsError {
+                    row: 2,
+                    column: -3,
+                    rows: -1..1,
+                    columns: -1..1,
+                    unfilled: false,
+                },
+                error
+            );
+            assert_eq!(
+                "position (row 2, column -3) is out of bounds for rows -1..1 and columns -1..1",
+                error.to_string()
+            );
+
+            assert!(vec2d.try_at_mut(-1, 1).is_err());
+        }
+
+        #[test]
+        fn try_at_describes_unfilled_cells() {
+            let mut vec2d = Vec2D::with_capacity_and_size(4, 2, 2);
+            vec2d.push('a');
+
+            assert_eq!(Ok(&'a'), vec2d.try_at(0, 0));
+
+            let error = vec2d.try_at(1, 0).unwrap_err();
+            assert!(error.unfilled);
+            assert_eq!(
+                "position (row 1, column 0) has not been filled yet",
+                error.to_string()
+            );
+            assert!(!vec2d.try_at(2, 0).unwrap_err().unfilled);
+        }
+    }
+
+    mod iterating {
+        use super::*;
+
+        #[test]
+        fn iter_works() {
+            let mut vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, // Row -1
+                    4, 5, 6, // Row 0
+                ],
+                2,
+                1,
+                1,
+                1,
+            );
+
+            assert_eq!(21, vec2d.iter().sum::<i32>());
+
+            vec2d.iter_mut().for_each(|value| *value *= 2);
+            assert_eq!(42, vec2d.iter().sum::<i32>());
+        }
+
+        #[test]
+        fn indexed_iter_yields_logical_positions() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, // Row -1
+                    4, 5, 6, // Row 0
+                ],
+                2,
+                1,
+                1,
+                1,
+            );
+
+            assert_eq!(
+                vec![
+                    ((-1, -1), &1),
+                    ((-1, 0), &2),
+                    ((-1, 1), &3),
+                    ((0, -1), &4),
+                    ((0, 0), &5),
+                    ((0, 1), &6),
+                ],
+                vec2d.indexed_iter().collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn indexed_iter_mut_yields_logical_positions() {
+            let mut vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, // Row -1
+                    4, 5, 6, // Row 0
+                ],
+                2,
+                1,
+                1,
+                1,
+            );
+
+            for ((row, column), value) in vec2d.indexed_iter_mut() {
+                *value = (row * 10 + column) as i32;
+            }
+
+            assert_eq!(
+                Vec2D::from_negative(vec![-11, -10, -9, -1, 0, 1], 2, 1, 1, 1),
+                vec2d
+            );
+        }
+
+        #[test]
+        fn rows_works() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, // Row -1
+                    4, 5, 6, // Row 0
+                ],
+                2,
+                1,
+                1,
+                1,
+            );
+
+            assert_eq!(
+                vec![&[1, 2, 3][..], &[4, 5, 6][..]],
+                vec2d.rows().collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn columns_works() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, // Row -1
+                    4, 5, 6, // Row 0
+                ],
+                2,
+                1,
+                1,
+                1,
+            );
+
+            assert_eq!(
+                vec![vec![&1, &4], vec![&2, &5], vec![&3, &6]],
+                vec2d
+                    .columns()
+                    .map(|column| column.collect::<Vec<_>>())
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn positions_where_works() {
+            let vec2d = Vec2D::from_negative(
+                vec![
+                    1, 2, 3, // Row -1
+                    4, 5, 6, // Row 0
+                ],
+                2,
+                1,
+                1,
                 1,
             );
 
-            *vec2d.growing_at_mut(0, -2) = 9;
+            assert_eq!(
+                vec![(-1, 0), (0, -1), (0, 1)],
+                vec2d
+                    .positions_where(|value| value % 2 == 0)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    mod rendering {
+        use super::*;
+
+        #[test]
+        fn display_works() {
+            let vec2d: Vec2D<char> = "#.#\n..#".parse().unwrap();
+            assert_eq!("#.#\n..#", vec2d.to_string());
+
+            let vec2d = Vec2D::from(vec![true, false, false, true], 2, 2);
+            assert_eq!("#.\n.#", vec2d.to_string());
+        }
+
+        #[test]
+        fn render_with_works() {
+            let vec2d = Vec2D::from(vec![1, 2, 3, 4], 2, 2);
+
+            assert_eq!(
+                "ab\ncd",
+                vec2d.render_with(|value| (b'a' + *value as u8 - 1) as char)
+            );
+        }
+
+        #[test]
+        fn render_with_axis_labels_and_overlay() {
+            let vec2d = Vec2D::from_negative(vec![false; 12], 2, 2, 2, 1);
+
+            let settings = RenderSettings {
+                axis_labels: true,
+                overlay: HashMap::from([((-1, -2), 'S'), ((0, -1), '>'), ((0, 0), 'E')]),
+            };
+
+            assert_eq!(
+                "   --\n   2101\n-1 S...\n 0 .>E.\n 1 ....",
+                vec2d.render_with_settings(|b| if *b { '#' } else { '.' }, &settings)
+            );
+        }
+    }
+
+    mod transforming {
+        use super::*;
+
+        #[test]
+        fn transpose_works() {
+            let vec2d: Vec2D<char> = "abc\ndef".parse().unwrap();
 
-            let expected = Vec2D::from_negative(
+            assert_eq!("ad\nbe\ncf", vec2d.transpose().to_string());
+        }
+
+        #[test]
+        fn rotating_works() {
+            let vec2d: Vec2D<char> = "abc\ndef".parse().unwrap();
+
+            assert_eq!("da\neb\nfc", vec2d.clone().rotate_cw().to_string());
+            assert_eq!("cf\nbe\nad", vec2d.clone().rotate_ccw().to_string());
+            assert_eq!("fed\ncba", vec2d.clone().rotate_180().to_string());
+            assert_eq!(vec2d.clone(), vec2d.rotate_cw().rotate_ccw());
+        }
+
+        #[test]
+        fn flipping_works() {
+            let vec2d: Vec2D<char> = "abc\ndef".parse().unwrap();
+
+            assert_eq!("cba\nfed", vec2d.clone().flip_horizontal().to_string());
+            assert_eq!("def\nabc", vec2d.flip_vertical().to_string());
+        }
+
+        #[test]
+        fn in_place_works_for_square_grids() {
+            let mut vec2d: Vec2D<char> = "ab\ncd".parse().unwrap();
+
+            vec2d.transpose_in_place();
+            assert_eq!("ac\nbd", vec2d.to_string());
+
+            vec2d.rotate_cw_in_place();
+            assert_eq!("ba\ndc", vec2d.to_string());
+        }
+
+        #[test]
+        fn transforming_swaps_the_negative_extents() {
+            let vec2d = Vec2D::from_negative(
                 vec![
-                    0, 0, 1, 2, //
-                    9, 0, 3, 4, //
+                    'a', 'b', 'c', // Row -1
+                    'd', 'e', 'f', // Row 0
                 ],
-                2,
+                1,
                 1,
                 2,
                 1,
             );
-            assert_eq!(expected, vec2d);
+
+            let rotated = vec2d.clone().rotate_cw();
+            assert_eq!(
+                (1, 2),
+                (rotated.negative_width(), rotated.negative_height())
+            );
+            assert_eq!((1, 1), rotated.size());
+            assert_eq!(Some(&'d'), rotated.at(-2, -1));
+
+            let flipped = vec2d.flip_horizontal();
+            assert_eq!(
+                (2, 1),
+                (flipped.negative_width(), flipped.negative_height())
+            );
+            assert_eq!(Some(&'c'), flipped.at(-1, -2));
         }
 
         #[test]
-        fn growing_height_with_negative_height_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
-                vec![
-                    4, //
-                    3, //
-                    2, //
-                    1, //
-                    0, //
-                ],
-                1,
-                1,
-                0,
-                4,
-            );
+        fn transforming_a_grown_grid_works() {
+            let mut vec2d: Vec2D<char> = "ab\ncd".parse().unwrap();
+            *vec2d.growing_at_mut(0, 2) = 'x';
 
-            *vec2d.growing_at_mut(3, 0) = 9;
+            vec2d.rotate_cw_in_place();
 
-            let expected = Vec2D::from_negative(
-                vec![
-                    4, //
-                    3, //
-                    2, //
-                    1, //
-                    0, // 0, 0
-                    0, //
-                    0, //
-                    9, //
-                ],
-                1,
-                4,
-                0,
-                4,
+            assert_eq!(
+                "ca\ndb\n.x",
+                vec2d.render_with(|c| if *c == '\0' { '.' } else { *c })
             );
-            assert_eq!(expected, vec2d);
         }
 
         #[test]
-        fn growing_to_negative_height_1_width() {
-            let mut vec2d = Vec2D::from(
+        #[should_panic(expected = "only 4 of the 2x3 cells of the grid are filled")]
+        fn transforming_a_partially_filled_grid_panics() {
+            let mut vec2d = Vec2D::with_capacity_and_size(6, 3, 2);
+            (0..4).for_each(|value| vec2d.push(value));
+
+            vec2d.rotate_cw_in_place();
+        }
+
+        #[test]
+        fn orientations_works() {
+            let orientations = "abc\ndef"
+                .parse::<Vec2D<char>>()
+                .unwrap()
+                .orientations()
+                .map(|orientation| orientation.to_string())
+                .collect::<Vec<_>>();
+
+            assert_eq!(
                 vec![
-                    1, //
-                    2, //
+                    "abc\ndef",
+                    "da\neb\nfc",
+                    "fed\ncba",
+                    "cf\nbe\nad",
+                    "cba\nfed",
+                    "fc\neb\nda",
+                    "def\nabc",
+                    "ad\nbe\ncf",
                 ],
-                1,
-                2,
+                orientations
             );
+        }
+    }
 
-            *vec2d.growing_at_mut(-3, 0) = 9;
+    mod index {
+        use super::*;
 
-            let expected = Vec2D::from_negative(
-                vec![
-                    9, //,
-                    0, //
-                    0, //
-                    1, //
-                    2, //
-                ],
-                1,
-                2,
-                0,
-                3,
+        #[test]
+        fn index_by_tuple_works() {
+            let mut vec2d = Vec2D::from_negative(vec![1, 2, 3, 4, 5, 6], 2, 1, 1, 1);
+
+            assert_eq!(1, vec2d[(-1_isize, -1_isize)]);
+            assert_eq!(6, vec2d[(0_isize, 1_isize)]);
+            assert_eq!(5, vec2d[(0_usize, 0_usize)]);
+
+            vec2d[(-1_isize, 1_isize)] = 9;
+            vec2d[(0_usize, 1_usize)] = 8;
+            assert_eq!(
+                Vec2D::from_negative(vec![1, 2, 9, 4, 5, 8], 2, 1, 1, 1),
+                vec2d
             );
+        }
 
-            assert_eq!(expected, vec2d);
+        #[test]
+        #[should_panic(
+            expected = "position (row -1, column 2) is out of bounds for rows -1..1 and columns -1..2 (positive_width: 2, positive_height: 1, negative_width: 1, negative_height: 1)"
+        )]
+        fn index_by_tuple_panics_with_the_extents() {
+            let vec2d = Vec2D::from_negative(vec![1, 2, 3, 4, 5, 6], 2, 1, 1, 1);
+
+            // Would otherwise be the first cell of the next row
+            let _ = vec2d[(-1_isize, 2_isize)];
         }
 
         #[test]
-        fn growing_to_negative_height_3_width() {
-            let mut vec2d = Vec2D::from(
+        #[should_panic(expected = "out of bounds")]
+        fn index_by_huge_usize_panics() {
+            let vec2d = Vec2D::from_negative(vec![1, 2, 3, 4], 1, 1, 1, 1);
+
+            let _ = vec2d[(usize::MAX, 0_usize)];
+        }
+
+        #[test]
+        fn index_by_point_works() {
+            let mut vec2d = Vec2D::from_negative(vec![1, 2, 3, 4], 1, 1, 1, 1);
+
+            assert_eq!(1, vec2d[Point::new(-1, -1)]);
+            assert_eq!(4, vec2d[Point::new(0, 0)]);
+
+            vec2d[Point::new(0, -1)] = 9;
+            assert_eq!(Some(&9), vec2d.at(0, -1));
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "position (row 0, column 1) is out of bounds for rows -1..1 and columns -1..1"
+        )]
+        fn index_by_point_panics_with_the_extents() {
+            let vec2d = Vec2D::from_negative(vec![1, 2, 3, 4], 1, 1, 1, 1);
+
+            let _ = vec2d[Point::new(0, 1)];
+        }
+    }
+
+    mod neighbours {
+        use super::*;
+
+        #[test]
+        fn neighbours4_works() {
+            let vec2d = Vec2D::from(
                 vec![
                     1, 2, 3, //
                     4, 5, 6, //
@@ -549,28 +2274,39 @@ mod tests {
                 3,
             );
 
-            *vec2d.growing_at_mut(-2, 2) = 9;
+            assert_eq!(
+                vec![(0, 1, &2), (1, 2, &6), (2, 1, &8), (1, 0, &4)],
+                vec2d.neighbours4(1, 1).collect::<Vec<_>>()
+            );
+        }
 
-            let expected = Vec2D::from_negative(
+        #[test]
+        fn neighbours8_skips_out_of_range_cells() {
+            let vec2d = Vec2D::from(
                 vec![
-                    0, 0, 9, //
-                    0, 0, 0, //
                     1, 2, 3, //
                     4, 5, 6, //
                     7, 8, 9, //
                 ],
                 3,
                 3,
-                0,
-                2,
             );
 
-            assert_eq!(expected, vec2d);
+            assert_eq!(
+                vec![(0, 1, &2), (1, 0, &4), (1, 1, &5)],
+                vec2d.neighbours8(0, 0).collect::<Vec<_>>()
+            );
+
+            // Does not leak into the next row
+            assert_eq!(
+                vec![(0, 1, &2), (1, 1, &5), (1, 2, &6)],
+                vec2d.neighbours8(0, 2).collect::<Vec<_>>()
+            );
         }
 
         #[test]
-        fn growing_to_negative_height_with_negative_width() {
-            let mut vec2d = Vec2D::from_negative(
+        fn neighbours_respect_negative_extents() {
+            let vec2d = Vec2D::from_negative(
                 vec![
                     1, 2, //
                     3, 4, //
@@ -581,80 +2317,225 @@ mod tests {
                 1,
             );
 
-            *vec2d.growing_at_mut(-3, -1) = 9;
+            assert_eq!(
+                vec![(-1, 0, &2), (0, -1, &3)],
+                vec2d.neighbours4(0, 0).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                vec![(-1, -1, &1), (-1, 0, &2), (0, -1, &3)],
+                vec2d.neighbours8(0, 0).collect::<Vec<_>>()
+            );
+        }
 
-            let expected = Vec2D::from_negative(
+        #[test]
+        fn neighbours_with_custom_offsets_works() {
+            let vec2d = Vec2D::from(
                 vec![
-                    9, 0, //
-                    0, 0, //
-                    1, 2, //
-                    3, 4, //
+                    1, 2, 3, //
+                    4, 5, 6, //
+                    7, 8, 9, //
                 ],
-                1,
-                1,
-                1,
+                3,
                 3,
             );
-            assert_eq!(expected, vec2d);
+            let knight = [
+                (-2, -1),
+                (-2, 1),
+                (2, -1),
+                (2, 1),
+                (-1, 2),
+                (1, 2),
+                (-1, -2),
+                (1, -2),
+            ];
+
+            assert_eq!(
+                vec![(2, 1, &8), (1, 2, &6)],
+                vec2d.neighbours_with(0, 0, &knight).collect::<Vec<_>>()
+            );
         }
 
         #[test]
-        fn growing_height_with_negative_width_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+        fn neighbours_mut_works() {
+            let mut vec2d = Vec2D::from(
                 vec![
                     1, 2, 3, //
+                    4, 5, 6, //
+                    7, 8, 9, //
                 ],
-                1,
-                1,
-                2,
-                0,
+                3,
+                3,
             );
 
-            *vec2d.growing_at_mut(2, -1) = 9;
+            for (_, _, value) in vec2d.neighbours4_mut(1, 1) {
+                *value *= 10;
+            }
 
-            let expected = Vec2D::from_negative(
+            let expected = Vec2D::from(
                 vec![
-                    1, 2, 3, //
-                    0, 0, 0, //
-                    0, 9, 0, //
+                    1, 20, 3, //
+                    40, 5, 60, //
+                    7, 80, 9, //
                 ],
-                1,
                 3,
-                2,
-                0,
+                3,
             );
             assert_eq!(expected, vec2d);
+
+            // Duplicate offsets only hand out the cell once
+            assert_eq!(
+                vec![(0, 1)],
+                vec2d
+                    .neighbours_with_mut(0, 0, &[(0, 1), (0, -1), (0, 1)])
+                    .map(|(r, c, _)| (r, c))
+                    .collect::<Vec<_>>()
+            );
         }
 
         #[test]
-        fn growing_height_with_negative_width_and_negative_height_works() {
-            let mut vec2d: Vec2D<i32> = Vec2D::from_negative(
+        fn neighbours_wrapping_works() {
+            let vec2d = Vec2D::from(
                 vec![
-                    1, 2, //
-                    3, 4, //
+                    1, 2, 3, //
+                    4, 5, 6, //
+                    7, 8, 9, //
                 ],
-                1,
-                1,
-                1,
-                1,
+                3,
+                3,
             );
 
-            *vec2d.growing_at_mut(2, 0) = 9;
+            assert_eq!(
+                vec![(2, 0, &7), (0, 1, &2), (1, 0, &4), (0, 2, &3)],
+                vec2d.neighbours4_wrapping(0, 0).collect::<Vec<_>>()
+            );
+            assert_eq!(8, vec2d.neighbours8_wrapping(0, 0).count());
+        }
 
-            let expected = Vec2D::from_negative(
+        #[test]
+        fn neighbours_wrapping_respect_negative_extents() {
+            let vec2d = Vec2D::from_negative(vec![1, 2, 3], 1, 1, 2, 0);
+
+            assert_eq!(
+                vec![(0, -2, &1), (0, -1, &2)],
+                vec2d
+                    .neighbours_with_wrapping(0, 0, &[(0, 1), (0, 2)])
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn neighbours_wrapping_mut_yields_each_cell_once() {
+            let mut vec2d = Vec2D::from(vec![1, 2], 2, 1);
+
+            for (_, _, value) in vec2d.neighbours_with_wrapping_mut(0, 0, &NEIGHBOURS4) {
+                *value += 1;
+            }
+
+            assert_eq!(Vec2D::from(vec![2, 3], 2, 1), vec2d);
+        }
+
+        #[test]
+        fn neighbours_wrapping_mut_works() {
+            let mut vec2d = Vec2D::from(
                 vec![
-                    1, 2, //
-                    3, 4, //
-                    0, 0, //
-                    0, 9, //
+                    1, 2, 3, //
+                    4, 5, 6, //
+                    7, 8, 9, //
                 ],
-                1,
                 3,
-                1,
-                1,
+                3,
+            );
+
+            for (_, _, value) in vec2d.neighbours4_wrapping_mut(0, 0) {
+                *value *= 10;
+            }
+
+            let expected = Vec2D::from(
+                vec![
+                    1, 20, 30, //
+                    40, 5, 6, //
+                    70, 8, 9, //
+                ],
+                3,
+                3,
+            );
+            assert_eq!(expected, vec2d);
+            assert_eq!(8, vec2d.neighbours8_wrapping_mut(2, 2).count());
+        }
+    }
+
+    mod parsing {
+        use super::*;
+
+        #[test]
+        fn it_parses_a_char_grid() {
+            let vec2d: Vec2D<char> = "#.#\n..#\n".parse().unwrap();
+
+            let expected = Vec2D::from(
+                vec![
+                    '#', '.', '#', //
+                    '.', '.', '#', //
+                ],
+                3,
+                2,
             );
             assert_eq!(expected, vec2d);
         }
+
+        #[test]
+        fn it_parses_a_digit_grid() {
+            let vec2d: Vec2D<u32> = "123\r\n456\r\n".parse().unwrap();
+
+            assert_eq!((3, 2), vec2d.size());
+            assert_eq!(&6, vec2d.at_unchecked(1, 2));
+
+            let vec2d: Vec2D<u8> = "98\n76".parse().unwrap();
+            assert_eq!(&7, vec2d.at_unchecked(1, 0));
+        }
+
+        #[test]
+        fn it_rejects_invalid_digits() {
+            assert_eq!(
+                Err(ParseGridError::InvalidCharacter {
+                    row: 1,
+                    column: 1,
+                    character: 'x',
+                }),
+                "12\n3x".parse::<Vec2D<u32>>()
+            );
+        }
+
+        #[test]
+        fn it_rejects_ragged_rows() {
+            assert_eq!(
+                Err(ParseGridError::RaggedRow {
+                    row: 2,
+                    expected: 3,
+                    found: 2,
+                }),
+                Vec2D::parse_grid("...\n...\n..\n", |c| c == '#')
+            );
+        }
+
+        #[test]
+        fn it_parses_an_empty_grid() {
+            let vec2d = Vec2D::parse_grid("", |c| c).unwrap();
+
+            assert_eq!((0, 0), vec2d.size());
+        }
+
+        #[test]
+        fn it_records_markers() {
+            let (vec2d, markers) =
+                Vec2D::parse_grid_with_markers("S.#\n#.E\n", &['S', 'E'], |c| c == '#').unwrap();
+
+            assert_eq!(
+                Vec2D::from(vec![false, false, true, true, false, false], 3, 2),
+                vec2d
+            );
+            assert_eq!(Some(&vec![(0, 0)]), markers.get(&'S'));
+            assert_eq!(Some(&vec![(1, 2)]), markers.get(&'E'));
+        }
     }
 
     #[test]